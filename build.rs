@@ -0,0 +1,13 @@
+const COMMANDS: &[&str] = &[
+    "show",
+    "hide",
+    "toggle",
+    "close",
+    "is_visible",
+    "set_level",
+    "set_alpha_value",
+];
+
+fn main() {
+    tauri_plugin::Builder::new(COMMANDS).build();
+}
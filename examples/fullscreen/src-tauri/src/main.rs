@@ -5,17 +5,13 @@
 
 use tauri::{AppHandle, Manager, WebviewWindow};
 use tauri_nspanel::{
-  cocoa::appkit::NSWindowCollectionBehavior, panel_delegate, ManagerExt, WebviewWindowExt,
+  panel_delegate, PanelCollectionBehavior, PanelEvent, PanelLevel, PanelStyleMask,
+  WebviewWindowExt,
 };
 
 fn main() {
   tauri::Builder::default()
     .plugin(tauri_nspanel::init())
-    .invoke_handler(tauri::generate_handler![
-      show_panel,
-      hide_panel,
-      close_panel
-    ])
     .setup(|app| {
       // Set activation poicy to Accessory to prevent the app icon from showing on the dock
       app.set_activation_policy(tauri::ActivationPolicy::Accessory);
@@ -41,60 +37,30 @@ fn init(app_handle: &AppHandle) {
 
   let handle = app_handle.to_owned();
 
-  delegate.set_listener(Box::new(move |delegate_name: String| {
-    match delegate_name.as_str() {
-      "window_did_become_key" => {
-        let app_name = handle.package_info().name.to_owned();
+  delegate.set_listener(Box::new(move |event: PanelEvent| match event {
+    PanelEvent::DidBecomeKey => {
+      let app_name = handle.package_info().name.to_owned();
 
-        println!("[info]: {:?} panel becomes key window!", app_name);
-      }
-      "window_did_resign_key" => {
-        println!("[info]: panel resigned from key window!");
-      }
-      _ => (),
+      println!("[info]: {:?} panel becomes key window!", app_name);
     }
+    PanelEvent::DidResignKey => {
+      println!("[info]: panel resigned from key window!");
+    }
+    _ => (),
   }));
 
   // Set the window to float level
-  #[allow(non_upper_case_globals)]
-  const NSFloatWindowLevel: i32 = 4;
-  panel.set_level(NSFloatWindowLevel);
+  panel.set_level(PanelLevel::Floating);
 
-  #[allow(non_upper_case_globals)]
-  const NSWindowStyleMaskNonActivatingPanel: i32 = 1 << 7;
   // Ensures the panel cannot activate the app
-  panel.set_style_mask(NSWindowStyleMaskNonActivatingPanel);
+  panel.set_style_mask(PanelStyleMask::NonActivatingPanel);
 
   // Allows the panel to:
   // - display on the same space as the full screen window
   // - join all spaces
   panel.set_collection_behaviour(
-    NSWindowCollectionBehavior::NSWindowCollectionBehaviorFullScreenAuxiliary
-      | NSWindowCollectionBehavior::NSWindowCollectionBehaviorCanJoinAllSpaces,
+    PanelCollectionBehavior::FullScreenAuxiliary | PanelCollectionBehavior::CanJoinAllSpaces,
   );
 
   panel.set_delegate(delegate);
 }
-
-#[tauri::command]
-fn show_panel(handle: AppHandle) {
-  let panel = handle.get_webview_panel("main").unwrap();
-
-  panel.show();
-}
-
-#[tauri::command]
-fn hide_panel(handle: AppHandle) {
-  let panel = handle.get_webview_panel("main").unwrap();
-
-  panel.order_out(None);
-}
-
-#[tauri::command]
-fn close_panel(handle: AppHandle) {
-  let panel = handle.get_webview_panel("main").unwrap();
-
-  panel.set_released_when_closed(true);
-
-  panel.close();
-}
@@ -4,16 +4,11 @@
 )]
 
 use tauri::{AppHandle, Manager, WebviewWindow};
-use tauri_nspanel::{panel_delegate, ManagerExt, WebviewWindowExt};
+use tauri_nspanel::{panel_delegate, PanelEvent, WebviewWindowExt};
 
 fn main() {
   tauri::Builder::default()
     .plugin(tauri_nspanel::init())
-    .invoke_handler(tauri::generate_handler![
-      show_panel,
-      hide_panel,
-      close_panel
-    ])
     .setup(|app| {
       init(app.app_handle());
 
@@ -35,42 +30,17 @@ fn init(app_handle: &AppHandle) {
 
   let handle = app_handle.to_owned();
 
-  delegate.set_listener(Box::new(move |delegate_name: String| {
-    match delegate_name.as_str() {
-      "window_did_become_key" => {
-        let app_name = handle.package_info().name.to_owned();
+  delegate.set_listener(Box::new(move |event: PanelEvent| match event {
+    PanelEvent::DidBecomeKey => {
+      let app_name = handle.package_info().name.to_owned();
 
-        println!("[info]: {:?} panel becomes key window!", app_name);
-      }
-      "window_did_resign_key" => {
-        println!("[info]: panel resigned from key window!");
-      }
-      _ => (),
+      println!("[info]: {:?} panel becomes key window!", app_name);
     }
+    PanelEvent::DidResignKey => {
+      println!("[info]: panel resigned from key window!");
+    }
+    _ => (),
   }));
 
   panel.set_delegate(delegate);
 }
-
-#[tauri::command]
-fn show_panel(handle: AppHandle) {
-  let panel = handle.get_webview_panel("main").unwrap();
-
-  panel.show();
-}
-
-#[tauri::command]
-fn hide_panel(handle: AppHandle) {
-  let panel = handle.get_webview_panel("main").unwrap();
-
-  panel.order_out(None);
-}
-
-#[tauri::command]
-fn close_panel(handle: AppHandle) {
-  let panel = handle.get_webview_panel("main").unwrap();
-
-  panel.set_released_when_closed(true);
-
-  panel.close();
-}
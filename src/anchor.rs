@@ -0,0 +1,222 @@
+use tauri::LogicalSize;
+#[cfg(feature = "tray-icon")]
+use tauri::{tray::TrayIconEvent, Rect};
+
+use crate::{PanelPoint, PanelRect};
+
+/// How a panel lines up horizontally with the rect it is anchored to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnchorAlignment {
+    /// Centers the panel under the rect.
+    #[default]
+    Center,
+    /// Aligns the left edges.
+    Leading,
+    /// Aligns the right edges.
+    Trailing,
+}
+
+/// Placement of a panel anchored to a rect, e.g. a tray icon.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AnchorOptions {
+    pub alignment: AnchorAlignment,
+    /// Moves the panel right by `x` and away from the rect by `y`.
+    pub offset: PanelPoint,
+    /// Minimum distance kept between the panel and the edges of the visible screen area.
+    pub margin: f64,
+}
+
+/// Returns the top-left position of a panel of `size` anchored below `anchor`, or above it when
+/// there isn't enough room below, e.g. for a tray icon in a bottom taskbar.
+///
+/// The panel is kept within `visible_area`, the visible frame of the screen. All coordinates are
+/// logical with a top-left origin.
+pub fn anchor_position(
+    anchor: PanelRect,
+    size: LogicalSize<f64>,
+    visible_area: PanelRect,
+    options: AnchorOptions,
+) -> PanelPoint {
+    let x = match options.alignment {
+        AnchorAlignment::Center => anchor.x + (anchor.width - size.width) / 2.0,
+        AnchorAlignment::Leading => anchor.x,
+        AnchorAlignment::Trailing => anchor.x + anchor.width - size.width,
+    } + options.offset.x;
+
+    let below = anchor.y + anchor.height + options.offset.y;
+    let above = anchor.y - size.height - options.offset.y;
+    let bottom = visible_area.y + visible_area.height - options.margin;
+
+    let y = if below + size.height > bottom && above >= visible_area.y + options.margin {
+        above
+    } else {
+        below
+    };
+
+    PanelPoint {
+        x: clamp(
+            x,
+            size.width,
+            visible_area.x,
+            visible_area.width,
+            options.margin,
+        ),
+        y: clamp(
+            y,
+            size.height,
+            visible_area.y,
+            visible_area.height,
+            options.margin,
+        ),
+    }
+}
+
+/// Clamps `start` so `start..start + length` stays within the area inset by `margin`, favoring
+/// the leading edge when it doesn't fit.
+fn clamp(start: f64, length: f64, area_start: f64, area_length: f64, margin: f64) -> f64 {
+    let min = area_start + margin;
+    let max = area_start + area_length - margin - length;
+
+    start.min(max).max(min)
+}
+
+/// Returns the rect of the tray icon that emitted `event`.
+#[cfg(feature = "tray-icon")]
+pub(crate) fn tray_icon_rect(event: &TrayIconEvent) -> Option<Rect> {
+    match event {
+        TrayIconEvent::Click { rect, .. }
+        | TrayIconEvent::DoubleClick { rect, .. }
+        | TrayIconEvent::Enter { rect, .. }
+        | TrayIconEvent::Move { rect, .. }
+        | TrayIconEvent::Leave { rect, .. } => Some(*rect),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 1440x900 primary screen with a 25 points menu bar.
+    const VISIBLE_AREA: PanelRect = PanelRect {
+        x: 0.0,
+        y: 25.0,
+        width: 1440.0,
+        height: 875.0,
+    };
+
+    const OPTIONS: AnchorOptions = AnchorOptions {
+        alignment: AnchorAlignment::Center,
+        offset: PanelPoint { x: 0.0, y: 0.0 },
+        margin: 8.0,
+    };
+
+    fn tray_icon(x: f64, y: f64) -> PanelRect {
+        PanelRect {
+            x,
+            y,
+            width: 24.0,
+            height: 24.0,
+        }
+    }
+
+    #[test]
+    fn centers_under_the_anchor() {
+        // The margin also keeps the panel off the menu bar
+        let position = anchor_position(
+            tray_icon(700.0, 0.0),
+            LogicalSize::new(300.0, 400.0),
+            VISIBLE_AREA,
+            OPTIONS,
+        );
+
+        assert_eq!(position, PanelPoint { x: 562.0, y: 33.0 });
+    }
+
+    #[test]
+    fn keeps_within_the_left_edge() {
+        let position = anchor_position(
+            tray_icon(5.0, 0.0),
+            LogicalSize::new(300.0, 400.0),
+            VISIBLE_AREA,
+            OPTIONS,
+        );
+
+        assert_eq!(position, PanelPoint { x: 8.0, y: 33.0 });
+    }
+
+    #[test]
+    fn keeps_within_the_right_edge() {
+        let position = anchor_position(
+            tray_icon(1410.0, 0.0),
+            LogicalSize::new(300.0, 400.0),
+            VISIBLE_AREA,
+            OPTIONS,
+        );
+
+        assert_eq!(position, PanelPoint { x: 1132.0, y: 33.0 });
+    }
+
+    #[test]
+    fn flips_above_a_bottom_taskbar() {
+        let visible_area = PanelRect {
+            x: 0.0,
+            y: 0.0,
+            width: 1920.0,
+            height: 1040.0,
+        };
+
+        let position = anchor_position(
+            tray_icon(1800.0, 1048.0),
+            LogicalSize::new(300.0, 400.0),
+            visible_area,
+            OPTIONS,
+        );
+
+        assert_eq!(
+            position,
+            PanelPoint {
+                x: 1612.0,
+                y: 632.0
+            }
+        );
+    }
+
+    #[test]
+    fn anchors_on_a_screen_with_a_negative_origin() {
+        // A 1920x1080 screen left of and above the primary one
+        let visible_area = PanelRect {
+            x: -1920.0,
+            y: -200.0,
+            width: 1920.0,
+            height: 1055.0,
+        };
+
+        let position = anchor_position(
+            tray_icon(-100.0, -200.0),
+            LogicalSize::new(300.0, 400.0),
+            visible_area,
+            OPTIONS,
+        );
+
+        assert_eq!(
+            position,
+            PanelPoint {
+                x: -308.0,
+                y: -176.0
+            }
+        );
+    }
+
+    #[test]
+    fn favors_the_top_left_of_a_panel_larger_than_the_screen() {
+        let position = anchor_position(
+            tray_icon(700.0, 0.0),
+            LogicalSize::new(1600.0, 1000.0),
+            VISIBLE_AREA,
+            OPTIONS,
+        );
+
+        assert_eq!(position, PanelPoint { x: 8.0, y: 33.0 });
+    }
+}
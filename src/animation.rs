@@ -0,0 +1,380 @@
+use std::{future::Future, time::Duration};
+
+use crate::PanelRect;
+
+/// Easing curve of an [`Animation`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    /// Cubic, starts slow.
+    EaseIn,
+    /// Cubic, ends slow.
+    #[default]
+    EaseOut,
+    /// Cubic, starts and ends slow.
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps the linear progress `t`, from `0.0` to `1.0`, onto the curve.
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::EaseInOut => 1.0 - (2.0 - 2.0 * t).powi(3) / 2.0,
+        }
+    }
+}
+
+/// A screen edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// How a panel appears and disappears. Every transition also fades the panel.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Transition {
+    #[default]
+    Fade,
+    /// Slides in from `edge` by `distance` points, and out towards it.
+    Slide { edge: Edge, distance: f64 },
+    /// Grows from `from` times its size around its center, and shrinks back to it.
+    Scale { from: f64 },
+}
+
+impl Transition {
+    /// Returns the hidden state of a panel whose visible frame is `frame`.
+    fn hidden(&self, frame: PanelRect) -> Keyframe {
+        let frame = match *self {
+            Transition::Fade => frame,
+            Transition::Slide { edge, distance } => {
+                let (dx, dy) = match edge {
+                    Edge::Top => (0.0, -distance),
+                    Edge::Bottom => (0.0, distance),
+                    Edge::Left => (-distance, 0.0),
+                    Edge::Right => (distance, 0.0),
+                };
+
+                PanelRect {
+                    x: frame.x + dx,
+                    y: frame.y + dy,
+                    ..frame
+                }
+            }
+            Transition::Scale { from } => {
+                let center = frame.center();
+                let width = frame.width * from;
+                let height = frame.height * from;
+
+                PanelRect {
+                    x: center.x - width / 2.0,
+                    y: center.y - height / 2.0,
+                    width,
+                    height,
+                }
+            }
+        };
+
+        Keyframe { alpha: 0.0, frame }
+    }
+}
+
+/// Whether an [`Animation`] shows or hides the panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationDirection {
+    In,
+    Out,
+}
+
+/// The state of a panel at a point of an [`Animation`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    /// Multiplies the alpha value of the panel.
+    pub alpha: f64,
+    /// In logical coordinates with a top-left origin.
+    pub frame: PanelRect,
+}
+
+impl Keyframe {
+    fn interpolate(&self, to: &Keyframe, t: f64) -> Keyframe {
+        let lerp = |from: f64, to: f64| from + (to - from) * t;
+
+        Keyframe {
+            alpha: lerp(self.alpha, to.alpha),
+            frame: PanelRect {
+                x: lerp(self.frame.x, to.frame.x),
+                y: lerp(self.frame.y, to.frame.y),
+                width: lerp(self.frame.width, to.frame.width),
+                height: lerp(self.frame.height, to.frame.height),
+            },
+        }
+    }
+}
+
+/// A show or hide transition, see `RawNSPanel::show_animated`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Animation {
+    pub transition: Transition,
+    /// Defaults to 200ms.
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self {
+            transition: Transition::default(),
+            duration: Duration::from_millis(200),
+            easing: Easing::default(),
+        }
+    }
+}
+
+impl Animation {
+    /// Returns the eased progress after `elapsed`, from `0.0` to `1.0`.
+    pub fn progress(&self, elapsed: Duration) -> f64 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+
+        self.easing
+            .apply(elapsed.as_secs_f64() / self.duration.as_secs_f64())
+    }
+
+    pub fn is_finished(&self, elapsed: Duration) -> bool {
+        elapsed >= self.duration
+    }
+
+    /// Returns the state of a panel whose visible frame is `frame`, `elapsed` into the animation.
+    pub fn keyframe(
+        &self,
+        frame: PanelRect,
+        elapsed: Duration,
+        direction: AnimationDirection,
+    ) -> Keyframe {
+        let hidden = self.transition.hidden(frame);
+        let shown = Keyframe { alpha: 1.0, frame };
+        let progress = self.progress(elapsed);
+
+        match direction {
+            AnimationDirection::In => hidden.interpolate(&shown, progress),
+            AnimationDirection::Out => shown.interpolate(&hidden, progress),
+        }
+    }
+
+    /// Replaces motion with a fade, as the system's reduce motion preference asks for.
+    pub fn with_reduced_motion(self) -> Self {
+        Self {
+            transition: Transition::Fade,
+            ..self
+        }
+    }
+}
+
+/// Returns an `on_complete` callback for `show_animated` or `hide_animated`, and a future that
+/// resolves once it is called, or dropped because another animation interrupted this one.
+pub(crate) fn completion() -> (
+    impl FnOnce() + Send + 'static,
+    impl Future<Output = ()> + Send + 'static,
+) {
+    let (tx, mut rx) = tauri::async_runtime::channel::<()>(1);
+
+    let on_complete = move || {
+        let _ = tx.try_send(());
+    };
+
+    (on_complete, async move {
+        rx.recv().await;
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: PanelRect = PanelRect {
+        x: 100.0,
+        y: 200.0,
+        width: 400.0,
+        height: 300.0,
+    };
+
+    fn animation(transition: Transition, easing: Easing) -> Animation {
+        Animation {
+            transition,
+            duration: Duration::from_millis(200),
+            easing,
+        }
+    }
+
+    #[test]
+    fn easings_start_and_end_on_the_curve() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            // Progress past either end is clamped
+            assert_eq!(easing.apply(-1.0), 0.0);
+            assert_eq!(easing.apply(2.0), 1.0);
+        }
+
+        assert_eq!(Easing::Linear.apply(0.5), 0.5);
+        assert_eq!(Easing::EaseIn.apply(0.5), 0.125);
+        assert_eq!(Easing::EaseOut.apply(0.5), 0.875);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn progress_eases_elapsed_time() {
+        let animation = animation(Transition::Fade, Easing::Linear);
+
+        assert_eq!(animation.progress(Duration::ZERO), 0.0);
+        assert_eq!(animation.progress(Duration::from_millis(50)), 0.25);
+        assert_eq!(animation.progress(Duration::from_millis(400)), 1.0);
+        assert!(!animation.is_finished(Duration::from_millis(199)));
+        assert!(animation.is_finished(Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn zero_duration_finishes_immediately() {
+        let animation = Animation {
+            duration: Duration::ZERO,
+            ..Animation::default()
+        };
+
+        assert_eq!(animation.progress(Duration::ZERO), 1.0);
+        assert!(animation.is_finished(Duration::ZERO));
+        assert_eq!(
+            animation.keyframe(FRAME, Duration::ZERO, AnimationDirection::In),
+            Keyframe {
+                alpha: 1.0,
+                frame: FRAME
+            }
+        );
+    }
+
+    #[test]
+    fn fades_in_and_out() {
+        let animation = animation(Transition::Fade, Easing::Linear);
+        let halfway = Duration::from_millis(100);
+
+        assert_eq!(
+            animation.keyframe(FRAME, Duration::ZERO, AnimationDirection::In),
+            Keyframe {
+                alpha: 0.0,
+                frame: FRAME
+            }
+        );
+        assert_eq!(
+            animation.keyframe(FRAME, halfway, AnimationDirection::In),
+            Keyframe {
+                alpha: 0.5,
+                frame: FRAME
+            }
+        );
+        assert_eq!(
+            animation.keyframe(FRAME, Duration::from_millis(200), AnimationDirection::Out),
+            Keyframe {
+                alpha: 0.0,
+                frame: FRAME
+            }
+        );
+    }
+
+    #[test]
+    fn slides_from_each_edge() {
+        let cases = [
+            (Edge::Top, 100.0, 188.0),
+            (Edge::Bottom, 100.0, 212.0),
+            (Edge::Left, 88.0, 200.0),
+            (Edge::Right, 112.0, 200.0),
+        ];
+
+        for (edge, x, y) in cases {
+            let animation = animation(
+                Transition::Slide {
+                    edge,
+                    distance: 12.0,
+                },
+                Easing::EaseOut,
+            );
+
+            let start = animation.keyframe(FRAME, Duration::ZERO, AnimationDirection::In);
+            let end = animation.keyframe(FRAME, animation.duration, AnimationDirection::In);
+
+            assert_eq!(start.frame, PanelRect { x, y, ..FRAME }, "{edge:?}");
+            assert_eq!(end.frame, FRAME, "{edge:?}");
+        }
+    }
+
+    #[test]
+    fn scales_around_the_center() {
+        let animation = animation(Transition::Scale { from: 0.5 }, Easing::Linear);
+
+        let hidden = animation.keyframe(FRAME, animation.duration, AnimationDirection::Out);
+
+        assert_eq!(
+            hidden.frame,
+            PanelRect {
+                x: 200.0,
+                y: 275.0,
+                width: 200.0,
+                height: 150.0,
+            }
+        );
+        assert_eq!(hidden.frame.center(), FRAME.center());
+    }
+
+    #[test]
+    fn hiding_reverses_showing() {
+        let animation = animation(
+            Transition::Slide {
+                edge: Edge::Top,
+                distance: 12.0,
+            },
+            Easing::Linear,
+        );
+        let elapsed = Duration::from_millis(50);
+
+        let showing = animation.keyframe(FRAME, elapsed, AnimationDirection::In);
+        let hiding =
+            animation.keyframe(FRAME, animation.duration - elapsed, AnimationDirection::Out);
+
+        assert_eq!(showing.frame, hiding.frame);
+        assert!((showing.alpha - hiding.alpha).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reduced_motion_only_fades() {
+        let animation = animation(Transition::Scale { from: 0.5 }, Easing::EaseIn);
+        let reduced = animation.with_reduced_motion();
+
+        assert_eq!(reduced.transition, Transition::Fade);
+        assert_eq!(reduced.duration, animation.duration);
+        assert_eq!(reduced.easing, animation.easing);
+    }
+
+    #[test]
+    fn completion_resolves_when_called_or_dropped() {
+        let (on_complete, completed) = completion();
+        on_complete();
+        tauri::async_runtime::block_on(completed);
+
+        // An interrupted animation drops `on_complete` without calling it
+        let (on_complete, completed) = completion();
+        drop(on_complete);
+        tauri::async_runtime::block_on(completed);
+    }
+}
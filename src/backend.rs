@@ -0,0 +1,66 @@
+use crate::{PanelLevel, PanelStyleMask};
+
+/// The panel operations the plugin relies on, independent of how a panel is implemented.
+///
+/// [`Panel`](crate::Panel) implements it on every platform. Plugin commands and
+/// [`ManagerExt::get_panel_backend`](crate::ManagerExt::get_panel_backend) only go through this
+/// trait, so registering a [`MockPanel`](crate::mock::MockPanel) with
+/// [`ManagerExt::register_panel_backend`](crate::ManagerExt::register_panel_backend) lets app
+/// logic be tested without AppKit.
+pub trait PanelBackend: Send + Sync {
+    fn show(&self);
+
+    fn hide(&self);
+
+    /// Closes the panel and releases it.
+    fn close(&self);
+
+    fn is_visible(&self) -> bool;
+
+    /// Hides the panel if it is visible and shows it otherwise, returning whether it is visible.
+    fn toggle(&self) -> bool {
+        if self.is_visible() {
+            self.hide();
+        } else {
+            self.show();
+        }
+
+        self.is_visible()
+    }
+
+    /// Shows the panel unless it is already visible, returning whether it is visible.
+    fn show_if_hidden(&self) -> bool {
+        if !self.is_visible() {
+            self.show();
+        }
+
+        self.is_visible()
+    }
+
+    /// Hides the panel if it is visible, returning whether it is visible.
+    fn hide_if_visible(&self) -> bool {
+        if self.is_visible() {
+            self.hide();
+        }
+
+        self.is_visible()
+    }
+
+    fn make_key(&self);
+
+    fn resign_key(&self);
+
+    fn is_key(&self) -> bool;
+
+    fn set_level(&self, level: PanelLevel);
+
+    fn level(&self) -> PanelLevel;
+
+    fn set_style_mask(&self, style_mask: PanelStyleMask);
+
+    fn style_mask(&self) -> PanelStyleMask;
+
+    fn set_alpha_value(&self, value: f64);
+
+    fn alpha_value(&self) -> f64;
+}
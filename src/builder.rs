@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use tauri::{Manager, Runtime, WebviewUrl, WebviewWindowBuilder};
+
+#[cfg(target_os = "macos")]
+use crate::MainThreadMarker;
+use crate::{
+    DismissPolicy, Error, FocusPolicy, Panel, PanelCapabilities, PanelCollectionBehavior,
+    PanelEventTarget, PanelLevel, PanelStyleMask, ScreenTarget, TrackingOptions, TrackingRect,
+    WebviewPanelConfig, WebviewWindowExt,
+};
+
+/// Creates a webview window and converts it to a panel in one step.
+///
+/// The window is created hidden, so it never shows up as a regular window before becoming a
+/// panel. Call `show()` on the returned panel to display it.
+///
+/// ```ignore
+/// let panel = PanelBuilder::new(app, "search", WebviewUrl::App("search.html".into()))
+///     .window(|window| window.inner_size(600.0, 400.0).decorations(false))
+///     .level(PanelLevel::Floating)
+///     .style_mask(PanelStyleMask::NonActivatingPanel)
+///     .build()?;
+/// ```
+pub struct PanelBuilder<'a, R: Runtime, M: Manager<R>> {
+    window: WebviewWindowBuilder<'a, R, M>,
+    config: WebviewPanelConfig,
+}
+
+impl<'a, R: Runtime, M: Manager<R>> PanelBuilder<'a, R, M> {
+    pub fn new<L: Into<String>>(manager: &'a M, label: L, url: WebviewUrl) -> Self {
+        Self {
+            window: WebviewWindowBuilder::new(manager, label, url).visible(false),
+            config: WebviewPanelConfig::default(),
+        }
+    }
+
+    /// Configures the underlying [`WebviewWindowBuilder`], e.g. its size or title.
+    ///
+    /// Making the window visible here defeats creating it hidden.
+    pub fn window<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(WebviewWindowBuilder<'a, R, M>) -> WebviewWindowBuilder<'a, R, M>,
+    {
+        self.window = f(self.window);
+        self
+    }
+
+    pub fn level(mut self, level: impl Into<PanelLevel>) -> Self {
+        self.config.level = Some(level.into());
+        self
+    }
+
+    pub fn style_mask(mut self, style_mask: impl Into<PanelStyleMask>) -> Self {
+        self.config.style_mask = Some(style_mask.into());
+        self
+    }
+
+    pub fn collection_behavior(mut self, behavior: impl Into<PanelCollectionBehavior>) -> Self {
+        self.config.collection_behavior = Some(behavior.into());
+        self
+    }
+
+    pub fn floating(mut self, floating: bool) -> Self {
+        self.config.floating = Some(floating);
+        self
+    }
+
+    pub fn hides_on_deactivate(mut self, hides_on_deactivate: bool) -> Self {
+        self.config.hides_on_deactivate = Some(hides_on_deactivate);
+        self
+    }
+
+    pub fn tracking_area(mut self, tracking_area: bool) -> Self {
+        self.config.with_tracking_area = tracking_area;
+        self
+    }
+
+    pub fn tracking_options(mut self, options: TrackingOptions) -> Self {
+        self.config.tracking_options = options;
+        self
+    }
+
+    pub fn tracking_rect(mut self, rect: TrackingRect) -> Self {
+        self.config.tracking_rect = rect;
+        self
+    }
+
+    pub fn capabilities(mut self, capabilities: PanelCapabilities) -> Self {
+        self.config.capabilities = capabilities;
+        self
+    }
+
+    pub fn focus_policy(mut self, policy: FocusPolicy) -> Self {
+        self.config.focus.policy = policy;
+        self
+    }
+
+    /// Sets the delays of [`FocusPolicy::KeyOnHover`].
+    pub fn focus_delays(mut self, enter: Duration, exit: Duration) -> Self {
+        self.config.focus.enter_delay = enter;
+        self.config.focus.exit_delay = exit;
+        self
+    }
+
+    pub fn screen_target(mut self, target: ScreenTarget) -> Self {
+        self.config.screen_target = Some(target);
+        self
+    }
+
+    pub fn dismiss_policy(mut self, policy: DismissPolicy) -> Self {
+        self.config.dismiss_policy = policy;
+        self
+    }
+
+    pub fn forward_events(mut self, target: PanelEventTarget) -> Self {
+        self.config.forward_events = Some(target);
+        self
+    }
+
+    pub fn forward_mouse_events(mut self, target: PanelEventTarget) -> Self {
+        self.config.forward_mouse_events = Some(target);
+        self
+    }
+
+    /// Creates the window and converts it, returning the registered panel.
+    ///
+    /// Must be called on the main thread, see [`WebviewWindowExt::to_panel`]. Elsewhere no window
+    /// is created, and if the conversion fails the window is destroyed again.
+    pub fn build(self) -> Result<Panel, Error> {
+        #[cfg(target_os = "macos")]
+        MainThreadMarker::new().ok_or(Error::NotOnMainThread)?;
+
+        let window = self.window.build()?;
+
+        window.to_panel_with_config(self.config).map_err(|error| {
+            let _ = window.destroy();
+            error
+        })
+    }
+}
@@ -0,0 +1,20 @@
+/// Whether a panel can become the key or main window.
+///
+/// Panels are converted to a subclass of `RawNSPanel` per distinct set of capabilities, so a HUD
+/// panel can refuse key status while a search panel accepts it. Only applies on macOS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PanelCapabilities {
+    /// Defaults to `true`, `false` keeps keyboard focus in the previous key window.
+    pub can_become_key_window: bool,
+    /// Defaults to `false`, like `NSPanel`.
+    pub can_become_main_window: bool,
+}
+
+impl Default for PanelCapabilities {
+    fn default() -> Self {
+        Self {
+            can_become_key_window: true,
+            can_become_main_window: false,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use bitflags::bitflags;
+#[cfg(target_os = "macos")]
+use cocoa::appkit::NSWindowCollectionBehavior;
+use serde::{Deserialize, Serialize};
+
+bitflags! {
+    /// Collection behavior of a panel, mirroring AppKit's `NSWindowCollectionBehavior`.
+    ///
+    /// Serializes to and from flag names, e.g. `"CanJoinAllSpaces | FullScreenAuxiliary"`.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub struct PanelCollectionBehavior: u64 {
+        const CanJoinAllSpaces = 1 << 0;
+        const MoveToActiveSpace = 1 << 1;
+        const Managed = 1 << 2;
+        const Transient = 1 << 3;
+        const Stationary = 1 << 4;
+        const ParticipatesInCycle = 1 << 5;
+        const IgnoresCycle = 1 << 6;
+        const FullScreenPrimary = 1 << 7;
+        const FullScreenAuxiliary = 1 << 8;
+        const FullScreenNone = 1 << 9;
+        const FullScreenAllowsTiling = 1 << 11;
+        const FullScreenDisallowsTiling = 1 << 12;
+    }
+}
+
+impl PanelCollectionBehavior {
+    /// AppKit's default behavior, the absence of any flag. It isn't a flag itself, a zero flag
+    /// would be contained in every behavior and dropped when serializing.
+    pub const DEFAULT: Self = Self::empty();
+}
+
+#[cfg(target_os = "macos")]
+impl From<NSWindowCollectionBehavior> for PanelCollectionBehavior {
+    fn from(behavior: NSWindowCollectionBehavior) -> Self {
+        PanelCollectionBehavior::from_bits_retain(behavior.bits())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_empty() {
+        assert!(PanelCollectionBehavior::DEFAULT.is_empty());
+        assert_eq!(
+            PanelCollectionBehavior::default(),
+            PanelCollectionBehavior::DEFAULT
+        );
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        for behavior in [
+            PanelCollectionBehavior::DEFAULT,
+            PanelCollectionBehavior::Transient,
+            PanelCollectionBehavior::CanJoinAllSpaces
+                | PanelCollectionBehavior::FullScreenAuxiliary,
+        ] {
+            let json = serde_json::to_string(&behavior).unwrap();
+
+            assert_eq!(
+                serde_json::from_str::<PanelCollectionBehavior>(&json).unwrap(),
+                behavior
+            );
+        }
+    }
+
+    #[test]
+    fn deserializes_flag_names() {
+        assert_eq!(
+            serde_json::from_str::<PanelCollectionBehavior>(
+                r#""CanJoinAllSpaces | FullScreenAuxiliary""#
+            )
+            .unwrap(),
+            PanelCollectionBehavior::CanJoinAllSpaces
+                | PanelCollectionBehavior::FullScreenAuxiliary
+        );
+        assert_eq!(
+            serde_json::from_str::<PanelCollectionBehavior>(r#""""#).unwrap(),
+            PanelCollectionBehavior::DEFAULT
+        );
+        assert!(serde_json::from_str::<PanelCollectionBehavior>(r#""Default""#).is_err());
+    }
+}
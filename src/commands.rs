@@ -0,0 +1,57 @@
+use tauri::{command, AppHandle, Runtime};
+
+use crate::{Error, ManagerExt, PanelLevel};
+
+#[command]
+pub(crate) fn show<R: Runtime>(app: AppHandle<R>, label: String) -> Result<(), Error> {
+    app.get_panel_backend(&label)?.show();
+
+    Ok(())
+}
+
+#[command]
+pub(crate) fn hide<R: Runtime>(app: AppHandle<R>, label: String) -> Result<(), Error> {
+    app.get_panel_backend(&label)?.hide();
+
+    Ok(())
+}
+
+/// Shows the panel if it is hidden and hides it otherwise, returning the new visibility.
+#[command]
+pub(crate) fn toggle<R: Runtime>(app: AppHandle<R>, label: String) -> Result<bool, Error> {
+    Ok(app.get_panel_backend(&label)?.toggle())
+}
+
+#[command]
+pub(crate) fn close<R: Runtime>(app: AppHandle<R>, label: String) -> Result<(), Error> {
+    app.get_panel_backend(&label)?.close();
+
+    Ok(())
+}
+
+#[command]
+pub(crate) fn is_visible<R: Runtime>(app: AppHandle<R>, label: String) -> Result<bool, Error> {
+    Ok(app.get_panel_backend(&label)?.is_visible())
+}
+
+#[command]
+pub(crate) fn set_level<R: Runtime>(
+    app: AppHandle<R>,
+    label: String,
+    level: PanelLevel,
+) -> Result<(), Error> {
+    app.get_panel_backend(&label)?.set_level(level);
+
+    Ok(())
+}
+
+#[command]
+pub(crate) fn set_alpha_value<R: Runtime>(
+    app: AppHandle<R>,
+    label: String,
+    value: f64,
+) -> Result<(), Error> {
+    app.get_panel_backend(&label)?.set_alpha_value(value);
+
+    Ok(())
+}
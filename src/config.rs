@@ -0,0 +1,249 @@
+use std::{collections::HashMap, time::Duration};
+
+use serde::Deserialize;
+
+use crate::{
+    DismissPolicy, FocusOptions, FocusPolicy, PanelCapabilities, PanelCollectionBehavior,
+    PanelEventTarget, PanelLevel, PanelStyleMask, ScreenTarget, TrackingOptions, TrackingRect,
+    WebviewPanelConfig,
+};
+
+/// Configuration of the plugin, read from `plugins.nspanel` in `tauri.conf.json`.
+///
+/// Webview windows whose label matches a key of `panels` are converted to panels as soon as
+/// they are created:
+///
+/// ```json
+/// {
+///   "plugins": {
+///     "nspanel": {
+///       "panels": {
+///         "main": {
+///           "level": "status",
+///           "styleMask": "NonActivatingPanel",
+///           "collectionBehavior": "CanJoinAllSpaces | FullScreenAuxiliary"
+///         }
+///       }
+///     }
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub panels: HashMap<String, PanelConfig>,
+}
+
+/// Declarative configuration of a single panel. Unset fields keep the defaults applied by
+/// [`WebviewWindowExt::to_panel`](crate::WebviewWindowExt::to_panel).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PanelConfig {
+    pub level: Option<PanelLevel>,
+    pub style_mask: Option<PanelStyleMask>,
+    pub collection_behavior: Option<PanelCollectionBehavior>,
+    pub floating: Option<bool>,
+    pub hides_on_deactivate: Option<bool>,
+    pub tracking_area: Option<bool>,
+    pub tracking_options: Option<TrackingOptions>,
+    pub tracking_rect: Option<TrackingRect>,
+    pub forward_events: Option<PanelEventTarget>,
+    pub forward_mouse_events: Option<PanelEventTarget>,
+    pub screen_target: Option<ScreenTarget>,
+    pub dismiss_policy: Option<DismissPolicy>,
+    pub can_become_key_window: Option<bool>,
+    pub can_become_main_window: Option<bool>,
+    pub focus_policy: Option<FocusPolicy>,
+    /// In milliseconds.
+    pub focus_enter_delay: Option<u64>,
+    /// In milliseconds.
+    pub focus_exit_delay: Option<u64>,
+}
+
+impl From<PanelConfig> for WebviewPanelConfig {
+    fn from(config: PanelConfig) -> Self {
+        let default = WebviewPanelConfig::default();
+
+        Self {
+            #[cfg(target_os = "macos")]
+            delegate: default.delegate,
+            with_tracking_area: config.tracking_area.unwrap_or(default.with_tracking_area),
+            tracking_options: config.tracking_options.unwrap_or(default.tracking_options),
+            tracking_rect: config.tracking_rect.unwrap_or(default.tracking_rect),
+            forward_events: config.forward_events,
+            forward_mouse_events: config.forward_mouse_events,
+            screen_target: config.screen_target,
+            dismiss_policy: config.dismiss_policy.unwrap_or(default.dismiss_policy),
+            level: config.level,
+            style_mask: config.style_mask,
+            collection_behavior: config.collection_behavior,
+            floating: config.floating,
+            hides_on_deactivate: config.hides_on_deactivate,
+            capabilities: PanelCapabilities {
+                can_become_key_window: config
+                    .can_become_key_window
+                    .unwrap_or(default.capabilities.can_become_key_window),
+                can_become_main_window: config
+                    .can_become_main_window
+                    .unwrap_or(default.capabilities.can_become_main_window),
+            },
+            focus: FocusOptions {
+                policy: config.focus_policy.unwrap_or(default.focus.policy),
+                enter_delay: config
+                    .focus_enter_delay
+                    .map_or(default.focus.enter_delay, Duration::from_millis),
+                exit_delay: config
+                    .focus_exit_delay
+                    .map_or(default.focus.exit_delay, Duration::from_millis),
+            },
+            conversion_policy: default.conversion_policy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PanelRect;
+
+    fn panel(json: &str) -> PanelConfig {
+        let config: Config =
+            serde_json::from_str(&format!(r#"{{ "panels": {{ "main": {json} }} }}"#)).unwrap();
+
+        config.panels["main"].clone()
+    }
+
+    #[test]
+    fn deserializes_panels() {
+        let config = panel(
+            r#"{
+                "level": "status",
+                "styleMask": "NonActivatingPanel",
+                "collectionBehavior": "CanJoinAllSpaces | FullScreenAuxiliary",
+                "floating": true,
+                "hidesOnDeactivate": false,
+                "trackingArea": true,
+                "trackingOptions": "MouseEnteredAndExited | ActiveAlways",
+                "trackingRect": "bounds",
+                "forwardEvents": "webview",
+                "forwardMouseEvents": "app",
+                "screenTarget": "cursorScreen",
+                "dismissPolicy": "ResignKey | Escape",
+                "canBecomeKeyWindow": true,
+                "canBecomeMainWindow": false,
+                "focusPolicy": "keyOnClick",
+                "focusEnterDelay": 100,
+                "focusExitDelay": 250
+            }"#,
+        );
+
+        assert_eq!(config.level, Some(PanelLevel::Status));
+        assert_eq!(config.style_mask, Some(PanelStyleMask::NonActivatingPanel));
+        assert_eq!(
+            config.collection_behavior,
+            Some(
+                PanelCollectionBehavior::CanJoinAllSpaces
+                    | PanelCollectionBehavior::FullScreenAuxiliary
+            )
+        );
+        assert_eq!(config.floating, Some(true));
+        assert_eq!(config.hides_on_deactivate, Some(false));
+        assert_eq!(config.tracking_area, Some(true));
+        assert_eq!(
+            config.tracking_options,
+            Some(TrackingOptions::MouseEnteredAndExited | TrackingOptions::ActiveAlways)
+        );
+        assert_eq!(config.tracking_rect, Some(TrackingRect::Bounds));
+        assert_eq!(config.forward_events, Some(PanelEventTarget::Webview));
+        assert_eq!(config.forward_mouse_events, Some(PanelEventTarget::App));
+        assert_eq!(config.screen_target, Some(ScreenTarget::CursorScreen));
+        assert_eq!(
+            config.dismiss_policy,
+            Some(DismissPolicy::ResignKey | DismissPolicy::Escape)
+        );
+        assert_eq!(config.can_become_key_window, Some(true));
+        assert_eq!(config.can_become_main_window, Some(false));
+        assert_eq!(config.focus_policy, Some(FocusPolicy::KeyOnClick));
+        assert_eq!(config.focus_enter_delay, Some(100));
+        assert_eq!(config.focus_exit_delay, Some(250));
+    }
+
+    #[test]
+    fn missing_panels_are_empty() {
+        let config: Config = serde_json::from_str("{}").unwrap();
+
+        assert!(config.panels.is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        assert!(serde_json::from_str::<Config>(r#"{ "panel": {} }"#).is_err());
+        assert!(
+            serde_json::from_str::<Config>(r#"{ "panels": { "main": { "lvl": "status" } } }"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_variants() {
+        assert!(serde_json::from_str::<PanelConfig>(r#"{ "level": "Status" }"#).is_err());
+        assert!(serde_json::from_str::<PanelConfig>(r#"{ "styleMask": "Borderless" }"#).is_err());
+        assert!(serde_json::from_str::<PanelConfig>(r#"{ "focusPolicy": "always" }"#).is_err());
+    }
+
+    #[test]
+    fn deserializes_data_variants() {
+        let config = panel(
+            r#"{
+                "level": { "relative": ["status", 1] },
+                "trackingRect": { "inset": { "top": 1, "left": 2, "bottom": 3, "right": 4 } },
+                "screenTarget": { "screenOf": { "x": 0, "y": 0, "width": 10, "height": 10 } }
+            }"#,
+        );
+
+        assert_eq!(
+            config.level,
+            Some(PanelLevel::Relative(Box::new(PanelLevel::Status), 1))
+        );
+        assert_eq!(
+            config.tracking_rect,
+            Some(TrackingRect::Inset {
+                top: 1.0,
+                left: 2.0,
+                bottom: 3.0,
+                right: 4.0
+            })
+        );
+        assert_eq!(
+            config.screen_target,
+            Some(ScreenTarget::ScreenOf(PanelRect {
+                x: 0.0,
+                y: 0.0,
+                width: 10.0,
+                height: 10.0
+            }))
+        );
+
+        assert_eq!(
+            panel(r#"{ "level": { "custom": 7 } }"#).level,
+            Some(PanelLevel::Custom(7))
+        );
+        assert_eq!(
+            panel(r#"{ "screenTarget": { "index": 1 } }"#).screen_target,
+            Some(ScreenTarget::Index(1))
+        );
+    }
+
+    #[test]
+    fn applies_defaults() {
+        let config = WebviewPanelConfig::from(panel(r#"{ "focusEnterDelay": 100 }"#));
+        let default = WebviewPanelConfig::default();
+
+        assert_eq!(config.focus.enter_delay, Duration::from_millis(100));
+        assert_eq!(config.focus.exit_delay, default.focus.exit_delay);
+        assert_eq!(config.focus.policy, default.focus.policy);
+        assert_eq!(config.dismiss_policy, DismissPolicy::empty());
+        assert_eq!(config.level, None);
+    }
+}
@@ -0,0 +1,18 @@
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+
+bitflags! {
+    /// When the plugin hides a panel on its own, like a popover.
+    ///
+    /// Serializes to and from flag names, e.g. `"ResignKey | Escape"`. Pin the panel to suspend
+    /// the policy, e.g. while a file dialog it opened is shown.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub struct DismissPolicy: u32 {
+        /// The panel stops being the key window, e.g. when the user clicks another window.
+        const ResignKey = 1 << 0;
+        /// The user presses Escape while the panel is the key window.
+        const Escape = 1 << 1;
+        /// The user switches to another app.
+        const AppDeactivate = 1 << 2;
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+
+#[derive(Debug)]
+pub enum Error {
+    /// No panel is registered under `label`.
+    PanelNotFound { label: String },
+    /// The panel `label` was closed and its window destroyed.
+    PanelClosed { label: String },
+    /// The webview window `label` is already a panel.
+    AlreadyConverted { label: String },
+    /// The native window of the webview window `label` couldn't be retrieved.
+    NativeHandleUnavailable { label: String },
+    /// The operation must run on the main thread.
+    NotOnMainThread,
+    /// The event loop stopped before the operation could run on the main thread.
+    MainThreadUnavailable,
+    /// The plugin wasn't registered with `tauri::Builder::plugin(tauri_nspanel::init())`.
+    PluginNotInitialized,
+    /// A thread panicked while holding the panel store lock.
+    StorePoisoned,
+    /// The operation has no equivalent on this platform, e.g. `set_alpha_value` off macOS.
+    /// `FallbackPanel` logs it as a warning, as its methods don't fail, like `RawNSPanel`'s.
+    Unsupported { operation: &'static str },
+    /// A Tauri operation failed, e.g. creating the window of a [`PanelBuilder`](crate::PanelBuilder).
+    Tauri(tauri::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::PanelNotFound { label } => write!(f, "panel `{label}` not found"),
+            Error::PanelClosed { label } => write!(f, "panel `{label}` was closed"),
+            Error::AlreadyConverted { label } => {
+                write!(f, "webview window `{label}` is already a panel")
+            }
+            Error::NativeHandleUnavailable { label } => {
+                write!(
+                    f,
+                    "native window of webview window `{label}` is unavailable"
+                )
+            }
+            Error::NotOnMainThread => write!(f, "panels can only be used on the main thread"),
+            Error::MainThreadUnavailable => {
+                write!(f, "the main thread event loop is unavailable")
+            }
+            Error::PluginNotInitialized => write!(f, "the nspanel plugin is not initialized"),
+            Error::StorePoisoned => write!(f, "the panel store is poisoned"),
+            Error::Unsupported { operation } => {
+                write!(f, "`{operation}` is not supported on this platform")
+            }
+            Error::Tauri(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Tauri(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<tauri::Error> for Error {
+    fn from(error: tauri::Error) -> Self {
+        Error::Tauri(error)
+    }
+}
+
+/// Serializes to the error message, so errors can be returned from commands.
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
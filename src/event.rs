@@ -0,0 +1,211 @@
+#[cfg(target_os = "macos")]
+use cocoa::{base::id, foundation::NSRect};
+#[cfg(target_os = "macos")]
+use objc::{msg_send, sel, sel_impl};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, EventTarget, Runtime, WebviewWindow};
+
+#[cfg(target_os = "macos")]
+use crate::raw_nspanel::primary_screen_height;
+use crate::{PanelPoint, PanelRect};
+
+/// `NSWindow` notifications observed by [`RawNSPanel::on_event`], paired with the snake case
+/// name of the matching delegate callback.
+///
+/// [`RawNSPanel::on_event`]: crate::raw_nspanel::RawNSPanel::on_event
+#[cfg(target_os = "macos")]
+pub(crate) const WINDOW_NOTIFICATIONS: &[(&str, &str)] = &[
+    ("NSWindowDidBecomeKeyNotification", "window_did_become_key"),
+    ("NSWindowDidResignKeyNotification", "window_did_resign_key"),
+    (
+        "NSWindowDidBecomeMainNotification",
+        "window_did_become_main",
+    ),
+    (
+        "NSWindowDidResignMainNotification",
+        "window_did_resign_main",
+    ),
+    ("NSWindowDidResizeNotification", "window_did_resize"),
+    ("NSWindowDidMoveNotification", "window_did_move"),
+    ("NSWindowWillCloseNotification", "window_will_close"),
+    (
+        "NSWindowDidChangeScreenNotification",
+        "window_did_change_screen",
+    ),
+    (
+        "NSWindowDidMiniaturizeNotification",
+        "window_did_miniaturize",
+    ),
+    (
+        "NSWindowDidDeminiaturizeNotification",
+        "window_did_deminiaturize",
+    ),
+    (
+        "NSWindowDidEnterFullScreenNotification",
+        "window_did_enter_full_screen",
+    ),
+    (
+        "NSWindowDidExitFullScreenNotification",
+        "window_did_exit_full_screen",
+    ),
+    ("NSWindowDidExposeNotification", "window_did_expose"),
+    (DID_SHOW_NOTIFICATION, "window_did_show"),
+    (DID_HIDE_NOTIFICATION, "window_did_hide"),
+];
+
+/// Posted by `RawNSPanel` when showing it makes it visible, AppKit has no such notification.
+#[cfg(target_os = "macos")]
+pub(crate) const DID_SHOW_NOTIFICATION: &str = "RawNSPanelDidShowNotification";
+
+/// Posted by `RawNSPanel` when hiding it makes it invisible.
+#[cfg(target_os = "macos")]
+pub(crate) const DID_HIDE_NOTIFICATION: &str = "RawNSPanelDidHideNotification";
+
+/// Where panel events are emitted when forwarding is enabled with
+/// [`WebviewPanelConfig::forward_events`].
+///
+/// [`WebviewPanelConfig::forward_events`]: crate::WebviewPanelConfig::forward_events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PanelEventTarget {
+    /// Only the panel's own webview receives the events.
+    Webview,
+    /// Every webview and app-wide listener receives the events.
+    App,
+}
+
+/// Payload of the `nspanel://<event>` events emitted to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct PanelEventPayload {
+    pub label: String,
+    pub event: PanelEvent,
+}
+
+/// An `NSWindowDelegate` callback received by a delegate created with [`panel_delegate!`].
+///
+/// Frames and origins have a top-left origin, like [`RawNSPanel::frame`].
+///
+/// [`panel_delegate!`]: crate::panel_delegate
+/// [`RawNSPanel::frame`]: crate::raw_nspanel::RawNSPanel::frame
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PanelEvent {
+    /// `windowDidBecomeKey:`
+    DidBecomeKey,
+    /// `windowDidResignKey:`
+    DidResignKey,
+    /// `windowDidBecomeMain:`
+    DidBecomeMain,
+    /// `windowDidResignMain:`
+    DidResignMain,
+    /// `windowDidResize:`
+    DidResize { frame: PanelRect },
+    /// `windowDidMove:`
+    DidMove { origin: PanelPoint },
+    /// `windowWillClose:`
+    WillClose,
+    /// `windowDidChangeScreen:`
+    DidChangeScreen,
+    /// `windowDidMiniaturize:`
+    DidMiniaturize,
+    /// `windowDidDeminiaturize:`
+    DidDeminiaturize,
+    /// `windowDidEnterFullScreen:`
+    DidEnterFullScreen,
+    /// `windowDidExitFullScreen:`
+    DidExitFullScreen,
+    /// The panel became visible. Posted by the plugin rather than AppKit, so it is only observed
+    /// with `on_event`, not by delegates.
+    Shown,
+    /// The panel stopped being visible, see [`PanelEvent::Shown`].
+    Hidden,
+    /// Any other delegate callback, by its snake case name, e.g. `window_did_expose`.
+    Other(String),
+}
+
+impl PanelEvent {
+    /// Builds an event from a snake case delegate name and the `NSNotification` passed to it.
+    #[cfg(target_os = "macos")]
+    pub fn from_notification(name: &str, notification: id) -> Self {
+        match name {
+            "window_did_become_key" => PanelEvent::DidBecomeKey,
+            "window_did_resign_key" => PanelEvent::DidResignKey,
+            "window_did_become_main" => PanelEvent::DidBecomeMain,
+            "window_did_resign_main" => PanelEvent::DidResignMain,
+            "window_did_resize" => PanelEvent::DidResize {
+                frame: notification_window_frame(notification),
+            },
+            "window_did_move" => PanelEvent::DidMove {
+                origin: notification_window_frame(notification).origin(),
+            },
+            "window_will_close" => PanelEvent::WillClose,
+            "window_did_change_screen" => PanelEvent::DidChangeScreen,
+            "window_did_miniaturize" => PanelEvent::DidMiniaturize,
+            "window_did_deminiaturize" => PanelEvent::DidDeminiaturize,
+            "window_did_enter_full_screen" => PanelEvent::DidEnterFullScreen,
+            "window_did_exit_full_screen" => PanelEvent::DidExitFullScreen,
+            "window_did_show" => PanelEvent::Shown,
+            "window_did_hide" => PanelEvent::Hidden,
+            name => PanelEvent::Other(name.to_owned()),
+        }
+    }
+
+    /// Returns the snake case delegate name of the event, e.g. `window_did_become_key`.
+    pub fn name(&self) -> &str {
+        match self {
+            PanelEvent::DidBecomeKey => "window_did_become_key",
+            PanelEvent::DidResignKey => "window_did_resign_key",
+            PanelEvent::DidBecomeMain => "window_did_become_main",
+            PanelEvent::DidResignMain => "window_did_resign_main",
+            PanelEvent::DidResize { .. } => "window_did_resize",
+            PanelEvent::DidMove { .. } => "window_did_move",
+            PanelEvent::WillClose => "window_will_close",
+            PanelEvent::DidChangeScreen => "window_did_change_screen",
+            PanelEvent::DidMiniaturize => "window_did_miniaturize",
+            PanelEvent::DidDeminiaturize => "window_did_deminiaturize",
+            PanelEvent::DidEnterFullScreen => "window_did_enter_full_screen",
+            PanelEvent::DidExitFullScreen => "window_did_exit_full_screen",
+            PanelEvent::Shown => "window_did_show",
+            PanelEvent::Hidden => "window_did_hide",
+            PanelEvent::Other(name) => name,
+        }
+    }
+
+    /// Returns the name of the Tauri event this event is forwarded as, e.g. `nspanel://did-become-key`.
+    pub fn event_name(&self) -> String {
+        let name = self.name();
+        let name = name.strip_prefix("window_").unwrap_or(name);
+
+        format!("nspanel://{}", name.replace('_', "-"))
+    }
+}
+
+/// Emits `event` as a Tauri event on behalf of the panel backed by `window`.
+pub(crate) fn forward<R: Runtime>(
+    window: &WebviewWindow<R>,
+    target: PanelEventTarget,
+    event: PanelEvent,
+) -> tauri::Result<()> {
+    let name = event.event_name();
+    let payload = PanelEventPayload {
+        label: window.label().to_owned(),
+        event,
+    };
+
+    match target {
+        PanelEventTarget::Webview => {
+            window.emit_to(EventTarget::webview_window(window.label()), &name, payload)
+        }
+        PanelEventTarget::App => window.emit(&name, payload),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn notification_window_frame(notification: id) -> PanelRect {
+    unsafe {
+        let window: id = msg_send![notification, object];
+        let frame: NSRect = msg_send![window, frame];
+
+        PanelRect::from(frame).to_top_left(primary_screen_height())
+    }
+}
@@ -0,0 +1,805 @@
+use std::{
+    ffi::c_void,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+#[cfg(feature = "tray-icon")]
+use tauri::tray::TrayIconEvent;
+use tauri::{
+    LogicalPosition, LogicalSize, Monitor, PhysicalPosition, PhysicalSize, Runtime, WebviewWindow,
+    WindowEvent,
+};
+
+#[cfg(feature = "tray-icon")]
+use crate::anchor::tray_icon_rect;
+use crate::{
+    anchor::anchor_position,
+    animation,
+    screen::{self, move_between_screens, PanelScreen, ScreenSource, ScreenTarget},
+    AnchorOptions, Animation, DismissPolicy, Error, FocusOptions, MouseTrackingEvent, PanelBackend,
+    PanelCollectionBehavior, PanelEvent, PanelLevel, PanelPoint, PanelRect, PanelStyleMask,
+    TrackingArea, TrackingAreaId, WebviewPanelConfig,
+};
+
+/// The subset of the [`WebviewWindow`] API a [`FallbackPanel`] maps onto, erased over the runtime.
+trait PortableWindow: Send + Sync {
+    fn show(&self) -> tauri::Result<()>;
+    fn hide(&self) -> tauri::Result<()>;
+    fn close(&self) -> tauri::Result<()>;
+    fn is_visible(&self) -> tauri::Result<bool>;
+    fn is_focused(&self) -> tauri::Result<bool>;
+    fn set_focus(&self) -> tauri::Result<()>;
+    fn set_always_on_top(&self, value: bool) -> tauri::Result<()>;
+    fn set_visible_on_all_workspaces(&self, value: bool) -> tauri::Result<()>;
+    fn set_size(&self, size: LogicalSize<f64>) -> tauri::Result<()>;
+    fn set_position(&self, position: LogicalPosition<f64>) -> tauri::Result<()>;
+    fn center(&self) -> tauri::Result<()>;
+    fn scale_factor(&self) -> tauri::Result<f64>;
+    fn outer_position(&self) -> tauri::Result<PhysicalPosition<i32>>;
+    fn outer_size(&self) -> tauri::Result<PhysicalSize<u32>>;
+    fn inner_position(&self) -> tauri::Result<PhysicalPosition<i32>>;
+    fn inner_size(&self) -> tauri::Result<PhysicalSize<u32>>;
+    fn available_monitors(&self) -> tauri::Result<Vec<Monitor>>;
+    fn cursor_position(&self) -> tauri::Result<PhysicalPosition<f64>>;
+    fn set_ignore_cursor_events(&self, value: bool) -> tauri::Result<()>;
+    fn set_shadow(&self, value: bool) -> tauri::Result<()>;
+    fn on_event(&self, callback: Box<dyn Fn(PanelEvent) + Send>);
+}
+
+impl<R: Runtime> PortableWindow for WebviewWindow<R> {
+    fn show(&self) -> tauri::Result<()> {
+        WebviewWindow::show(self)
+    }
+
+    fn hide(&self) -> tauri::Result<()> {
+        WebviewWindow::hide(self)
+    }
+
+    fn close(&self) -> tauri::Result<()> {
+        WebviewWindow::close(self)
+    }
+
+    fn is_visible(&self) -> tauri::Result<bool> {
+        WebviewWindow::is_visible(self)
+    }
+
+    fn is_focused(&self) -> tauri::Result<bool> {
+        WebviewWindow::is_focused(self)
+    }
+
+    fn set_focus(&self) -> tauri::Result<()> {
+        WebviewWindow::set_focus(self)
+    }
+
+    fn set_always_on_top(&self, value: bool) -> tauri::Result<()> {
+        WebviewWindow::set_always_on_top(self, value)
+    }
+
+    fn set_visible_on_all_workspaces(&self, value: bool) -> tauri::Result<()> {
+        WebviewWindow::set_visible_on_all_workspaces(self, value)
+    }
+
+    fn set_size(&self, size: LogicalSize<f64>) -> tauri::Result<()> {
+        WebviewWindow::set_size(self, size)
+    }
+
+    fn set_position(&self, position: LogicalPosition<f64>) -> tauri::Result<()> {
+        WebviewWindow::set_position(self, position)
+    }
+
+    fn center(&self) -> tauri::Result<()> {
+        WebviewWindow::center(self)
+    }
+
+    fn scale_factor(&self) -> tauri::Result<f64> {
+        WebviewWindow::scale_factor(self)
+    }
+
+    fn outer_position(&self) -> tauri::Result<PhysicalPosition<i32>> {
+        WebviewWindow::outer_position(self)
+    }
+
+    fn outer_size(&self) -> tauri::Result<PhysicalSize<u32>> {
+        WebviewWindow::outer_size(self)
+    }
+
+    fn inner_position(&self) -> tauri::Result<PhysicalPosition<i32>> {
+        WebviewWindow::inner_position(self)
+    }
+
+    fn inner_size(&self) -> tauri::Result<PhysicalSize<u32>> {
+        WebviewWindow::inner_size(self)
+    }
+
+    fn available_monitors(&self) -> tauri::Result<Vec<Monitor>> {
+        WebviewWindow::available_monitors(self)
+    }
+
+    fn cursor_position(&self) -> tauri::Result<PhysicalPosition<f64>> {
+        WebviewWindow::cursor_position(self)
+    }
+
+    fn set_ignore_cursor_events(&self, value: bool) -> tauri::Result<()> {
+        WebviewWindow::set_ignore_cursor_events(self, value)
+    }
+
+    fn set_shadow(&self, value: bool) -> tauri::Result<()> {
+        WebviewWindow::set_shadow(self, value)
+    }
+
+    fn on_event(&self, callback: Box<dyn Fn(PanelEvent) + Send>) {
+        let window = self.clone();
+
+        self.on_window_event(move |event| {
+            let scale_factor = window.scale_factor().unwrap_or(1.0);
+            let origin = window
+                .outer_position()
+                .map(|position| position.to_logical::<f64>(scale_factor))
+                .unwrap_or_default();
+
+            let event = match event {
+                WindowEvent::Focused(true) => PanelEvent::DidBecomeKey,
+                WindowEvent::Focused(false) => PanelEvent::DidResignKey,
+                WindowEvent::Moved(_) => PanelEvent::DidMove {
+                    origin: PanelPoint {
+                        x: origin.x,
+                        y: origin.y,
+                    },
+                },
+                WindowEvent::Resized(size) => {
+                    let size = size.to_logical::<f64>(scale_factor);
+
+                    PanelEvent::DidResize {
+                        frame: PanelRect {
+                            x: origin.x,
+                            y: origin.y,
+                            width: size.width,
+                            height: size.height,
+                        },
+                    }
+                }
+                WindowEvent::ScaleFactorChanged { .. } => PanelEvent::DidChangeScreen,
+                WindowEvent::Destroyed => PanelEvent::WillClose,
+                _ => return,
+            };
+
+            callback(event);
+        });
+    }
+}
+
+type EventListener = Arc<dyn Fn(PanelEvent) + Send + Sync>;
+
+struct FallbackState {
+    level: PanelLevel,
+    style_mask: PanelStyleMask,
+    collection_behaviour: PanelCollectionBehavior,
+    floating: bool,
+    focus: FocusOptions,
+    screen_target: Option<ScreenTarget>,
+    dismiss: DismissPolicy,
+    pinned: bool,
+    /// Listeners added by [`FallbackPanel::on_event`], which also receive
+    /// [`PanelEvent::Shown`] and [`PanelEvent::Hidden`].
+    listeners: Vec<EventListener>,
+}
+
+impl Default for FallbackState {
+    fn default() -> Self {
+        Self {
+            level: PanelLevel::default(),
+            style_mask: PanelStyleMask::default(),
+            collection_behaviour: PanelCollectionBehavior::default(),
+            floating: false,
+            focus: FocusOptions::default(),
+            screen_target: None,
+            dismiss: DismissPolicy::empty(),
+            pinned: false,
+            listeners: Vec::new(),
+        }
+    }
+}
+
+/// Stand-in for [`Panel`](crate::Panel) on platforms without `NSPanel`.
+///
+/// Exposes the same methods as `RawNSPanel` so shared app code compiles unchanged. Operations
+/// with a portable equivalent map onto the [`WebviewWindow`] API, e.g. floating levels keep the
+/// window always on top and `make_key_window` focuses it. The rest only record their value or
+/// do nothing.
+pub struct FallbackPanel {
+    window: Box<dyn PortableWindow>,
+    state: Arc<Mutex<FallbackState>>,
+}
+
+impl FallbackPanel {
+    pub(crate) fn from_window<R: Runtime>(
+        window: WebviewWindow<R>,
+        config: WebviewPanelConfig,
+    ) -> Self {
+        let state = Arc::<Mutex<FallbackState>>::default();
+
+        // Windows only report losing focus, which stands for both the window resigning key and
+        // the app being deactivated
+        let dismiss_state = state.clone();
+        let dismiss_window = window.clone();
+        window.on_window_event(move |event| {
+            if let WindowEvent::Focused(false) = event {
+                let dismiss = {
+                    let state = dismiss_state.lock().unwrap();
+                    state
+                        .dismiss
+                        .intersects(DismissPolicy::ResignKey | DismissPolicy::AppDeactivate)
+                        && !state.pinned
+                };
+
+                if dismiss && dismiss_window.is_visible().unwrap_or(false) {
+                    match dismiss_window.hide() {
+                        Ok(()) => emit(&dismiss_state, PanelEvent::Hidden),
+                        Err(error) => log::warn!(
+                            "failed to dismiss panel `{}`: {error}",
+                            dismiss_window.label()
+                        ),
+                    }
+                }
+            }
+        });
+
+        let panel = Self {
+            window: Box::new(window),
+            state,
+        };
+
+        if config.level.is_none() {
+            panel.set_level(PanelLevel::default());
+        }
+
+        panel.apply_config(&config);
+
+        panel
+    }
+
+    /// Applies the settings of `config` that can change after conversion, like
+    /// `RawNSPanel::apply_config`.
+    pub(crate) fn apply_config(&self, config: &WebviewPanelConfig) {
+        self.set_focus_options(config.focus);
+        self.set_screen_target(config.screen_target);
+        self.set_dismiss_policy(config.dismiss_policy);
+
+        if let Some(level) = config.level.clone() {
+            self.set_level(level);
+        }
+
+        if let Some(style_mask) = config.style_mask {
+            self.set_style_mask(style_mask);
+        }
+
+        if let Some(behaviour) = config.collection_behavior {
+            self.set_collection_behaviour(behaviour);
+        }
+
+        if let Some(floating) = config.floating {
+            self.set_floating_panel(floating);
+        }
+
+        if let Some(hides_on_deactivate) = config.hides_on_deactivate {
+            self.set_hides_on_deactivate(hides_on_deactivate);
+        }
+    }
+
+    /// Runs `f` and emits [`PanelEvent::Shown`] or [`PanelEvent::Hidden`] if it changed whether
+    /// the window is visible.
+    fn notify_visibility(&self, operation: &str, f: impl FnOnce() -> tauri::Result<()>) {
+        let was_visible = self.is_visible();
+
+        if let Err(error) = f() {
+            return log_failure(operation, error);
+        }
+
+        match (was_visible, self.is_visible()) {
+            (false, true) => emit(&self.state, PanelEvent::Shown),
+            (true, false) => emit(&self.state, PanelEvent::Hidden),
+            _ => {}
+        }
+    }
+
+    fn update_always_on_top(&self, state: &FallbackState) {
+        let always_on_top = state.floating || state.level.value() > PanelLevel::Normal.value();
+
+        if let Err(error) = self.window.set_always_on_top(always_on_top) {
+            log_failure("set_level", error);
+        }
+    }
+
+    pub fn show(&self) {
+        if let Some(target) = self.screen_target() {
+            self.move_to_screen(target);
+        }
+
+        self.order_front();
+    }
+
+    pub fn show_on(&self, target: ScreenTarget) {
+        self.move_to_screen(target);
+        self.order_front();
+    }
+
+    pub fn move_to_screen(&self, target: ScreenTarget) {
+        let Some(to) = target.resolve(self) else {
+            return;
+        };
+
+        let frame = self.frame();
+        let screens = self.screens();
+
+        let Some(from) = screen::screen_of(&screens, frame) else {
+            return;
+        };
+
+        if *from != to {
+            self.set_frame(move_between_screens(frame, from, &to), false);
+        }
+    }
+
+    pub fn set_screen_target(&self, target: Option<ScreenTarget>) {
+        self.state.lock().unwrap().screen_target = target;
+    }
+
+    pub fn screen_target(&self) -> Option<ScreenTarget> {
+        self.state.lock().unwrap().screen_target
+    }
+
+    /// Shows the panel without a transition, as windows can't be animated portably, then calls
+    /// `on_complete`.
+    pub fn show_animated<F: FnOnce() + 'static>(&self, _animation: Animation, on_complete: F) {
+        self.show();
+        on_complete();
+    }
+
+    /// Hides the panel without a transition, then calls `on_complete`.
+    pub fn hide_animated<F: FnOnce() + 'static>(&self, _animation: Animation, on_complete: F) {
+        self.order_out(None);
+        on_complete();
+    }
+
+    /// Shows the panel without a transition and returns a future that resolves right away.
+    pub fn show_animated_async(
+        &self,
+        animation: Animation,
+    ) -> impl Future<Output = ()> + Send + 'static {
+        let (on_complete, completed) = animation::completion();
+        self.show_animated(animation, on_complete);
+
+        completed
+    }
+
+    /// Hides the panel without a transition, see [`FallbackPanel::show_animated_async`].
+    pub fn hide_animated_async(
+        &self,
+        animation: Animation,
+    ) -> impl Future<Output = ()> + Send + 'static {
+        let (on_complete, completed) = animation::completion();
+        self.hide_animated(animation, on_complete);
+
+        completed
+    }
+
+    /// Sets when the panel hides itself, see [`DismissPolicy`]. Windows don't report key presses
+    /// to the plugin, so [`DismissPolicy::Escape`] has no effect.
+    pub fn set_dismiss_policy(&self, policy: DismissPolicy) {
+        self.state.lock().unwrap().dismiss = policy;
+    }
+
+    pub fn dismiss_policy(&self) -> DismissPolicy {
+        self.state.lock().unwrap().dismiss
+    }
+
+    /// Suspends the dismiss policy while `pinned`, e.g. while the panel shows a dialog.
+    pub fn set_pinned(&self, pinned: bool) {
+        self.state.lock().unwrap().pinned = pinned;
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.state.lock().unwrap().pinned
+    }
+
+    fn order_front(&self) {
+        self.order_front_regardless();
+
+        if self.focus_options().policy.key_on_show() {
+            self.make_key_window();
+        }
+    }
+
+    /// Only [`FocusPolicy::KeyOnShow`](crate::FocusPolicy::KeyOnShow) and the focus on show of
+    /// [`FocusPolicy::KeyOnHover`](crate::FocusPolicy::KeyOnHover) apply, windows don't track
+    /// hovering.
+    pub fn set_focus_options(&self, options: impl Into<FocusOptions>) {
+        self.state.lock().unwrap().focus = options.into();
+    }
+
+    pub fn focus_options(&self) -> FocusOptions {
+        self.state.lock().unwrap().focus
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.window.is_visible().unwrap_or(false)
+    }
+
+    /// Hides the panel if it is visible and shows it otherwise, returning whether it is visible.
+    pub fn toggle(&self) -> bool {
+        if self.is_visible() {
+            self.order_out(None);
+        } else {
+            self.show();
+        }
+
+        self.is_visible()
+    }
+
+    /// Shows the panel unless it is already visible, returning whether it is visible.
+    pub fn show_if_hidden(&self) -> bool {
+        if !self.is_visible() {
+            self.show();
+        }
+
+        self.is_visible()
+    }
+
+    /// Hides the panel if it is visible, returning whether it is visible.
+    pub fn hide_if_visible(&self) -> bool {
+        if self.is_visible() {
+            self.order_out(None);
+        }
+
+        self.is_visible()
+    }
+
+    pub fn is_floating_panel(&self) -> bool {
+        self.state.lock().unwrap().floating
+    }
+
+    pub fn is_key_window(&self) -> bool {
+        self.window.is_focused().unwrap_or(false)
+    }
+
+    pub fn make_key_window(&self) {
+        if let Err(error) = self.window.set_focus() {
+            log_failure("make_key_window", error);
+        }
+    }
+
+    /// Logs [`Error::Unsupported`], a window can't give up focus without focusing another one.
+    pub fn resign_key_window(&self) {
+        log_unsupported("resign_key_window");
+    }
+
+    pub fn make_key_and_order_front(&self, _sender: Option<*mut c_void>) {
+        self.show();
+    }
+
+    pub fn order_front_regardless(&self) {
+        self.notify_visibility("order_front_regardless", || self.window.show());
+    }
+
+    pub fn order_out(&self, _sender: Option<*mut c_void>) {
+        self.notify_visibility("order_out", || self.window.hide());
+    }
+
+    pub fn set_level(&self, level: impl Into<PanelLevel>) {
+        let mut state = self.state.lock().unwrap();
+
+        state.level = level.into();
+        self.update_always_on_top(&state);
+    }
+
+    pub fn level(&self) -> PanelLevel {
+        self.state.lock().unwrap().level.clone()
+    }
+
+    /// Logs [`Error::Unsupported`], window opacity has no portable equivalent.
+    pub fn set_alpha_value(&self, _value: f64) {
+        log_unsupported("set_alpha_value");
+    }
+
+    /// Always `1.0`, see [`FallbackPanel::set_alpha_value`].
+    pub fn alpha_value(&self) -> f64 {
+        1.0
+    }
+
+    pub fn set_content_size(&self, width: f64, height: f64) {
+        if let Err(error) = self.window.set_size(LogicalSize::new(width, height)) {
+            log_failure("set_content_size", error);
+        }
+    }
+
+    /// Returns the outer frame of the window in logical coordinates, with a top-left origin.
+    pub fn frame(&self) -> PanelRect {
+        let scale_factor = self.window.scale_factor().unwrap_or(1.0);
+
+        match (self.window.outer_position(), self.window.outer_size()) {
+            (Ok(position), Ok(size)) => PanelRect::from_physical(position, size, scale_factor),
+            _ => PanelRect::default(),
+        }
+    }
+
+    /// Moves and resizes the window, `animate` is ignored.
+    pub fn set_frame(&self, rect: PanelRect, _animate: bool) {
+        let result = self
+            .window
+            .set_position(rect.origin().into())
+            .and_then(|()| self.window.set_size(rect.size()));
+
+        if let Err(error) = result {
+            log_failure("set_frame", error);
+        }
+    }
+
+    pub fn set_position(&self, position: PanelPoint) {
+        if let Err(error) = self.window.set_position(position.into()) {
+            log_failure("set_position", error);
+        }
+    }
+
+    pub fn center(&self) {
+        if let Err(error) = self.window.center() {
+            log_failure("center", error);
+        }
+    }
+
+    pub fn content_rect(&self) -> PanelRect {
+        let scale_factor = self.window.scale_factor().unwrap_or(1.0);
+
+        match (self.window.inner_position(), self.window.inner_size()) {
+            (Ok(position), Ok(size)) => PanelRect::from_physical(position, size, scale_factor),
+            _ => PanelRect::default(),
+        }
+    }
+
+    /// Moves the window next to `rect`, keeping it within the screen `rect` is on. Unlike
+    /// `RawNSPanel`, the area taken by taskbars isn't known and may be overlapped.
+    pub fn anchor_to_rect(&self, rect: PanelRect, options: AnchorOptions) {
+        let screens = self.screens();
+
+        let Some(screen) = screen::screen_at(&screens, rect.center()) else {
+            return;
+        };
+
+        let position = anchor_position(rect, self.frame().size(), screen.visible_frame, options);
+
+        self.set_position(position);
+    }
+
+    #[cfg(feature = "tray-icon")]
+    pub fn anchor_to_tray(&self, event: &TrayIconEvent, options: AnchorOptions) {
+        let Some(rect) = tray_icon_rect(event) else {
+            return;
+        };
+
+        self.anchor_to_rect(screen::logical_rect(rect, &self.screens()), options);
+    }
+
+    /// Only records the style mask, see [`FallbackPanel::style_mask`].
+    pub fn set_style_mask(&self, style_mask: impl Into<PanelStyleMask>) {
+        self.state.lock().unwrap().style_mask = style_mask.into();
+    }
+
+    pub fn style_mask(&self) -> PanelStyleMask {
+        self.state.lock().unwrap().style_mask
+    }
+
+    pub fn insert_style_mask(&self, style_mask: PanelStyleMask) {
+        self.set_style_mask(self.style_mask() | style_mask);
+    }
+
+    pub fn remove_style_mask(&self, style_mask: PanelStyleMask) {
+        self.set_style_mask(self.style_mask() - style_mask);
+    }
+
+    /// Maps [`PanelCollectionBehavior::CanJoinAllSpaces`] to visibility on all workspaces.
+    pub fn set_collection_behaviour(&self, behaviour: impl Into<PanelCollectionBehavior>) {
+        let behaviour = behaviour.into();
+
+        if let Err(error) = self.window.set_visible_on_all_workspaces(
+            behaviour.contains(PanelCollectionBehavior::CanJoinAllSpaces),
+        ) {
+            log_failure("set_collection_behaviour", error);
+        }
+
+        self.state.lock().unwrap().collection_behaviour = behaviour;
+    }
+
+    pub fn collection_behaviour(&self) -> PanelCollectionBehavior {
+        self.state.lock().unwrap().collection_behaviour
+    }
+
+    /// Calls `callback` with the [`PanelEvent`]s that have a window event equivalent, and with
+    /// [`PanelEvent::Shown`] and [`PanelEvent::Hidden`] when the panel shows or hides the window.
+    ///
+    /// Frames and origins are in logical coordinates with a top-left origin.
+    pub fn on_event<F: Fn(PanelEvent) + Send + Sync + 'static>(&self, callback: F) {
+        let callback: EventListener = Arc::new(callback);
+
+        self.state.lock().unwrap().listeners.push(callback.clone());
+        self.window.on_event(Box::new(move |event| callback(event)));
+    }
+
+    /// Does nothing, windows have no tracking areas.
+    pub fn add_tracking_area(&self, _area: TrackingArea) -> TrackingAreaId {
+        TrackingAreaId(0)
+    }
+
+    /// Does nothing and returns `false`.
+    pub fn remove_tracking_area(&self, _tracking_id: TrackingAreaId) -> bool {
+        false
+    }
+
+    /// Does nothing and returns `false`.
+    pub fn replace_tracking_area(&self, _tracking_id: TrackingAreaId, _area: TrackingArea) -> bool {
+        false
+    }
+
+    /// Does nothing, windows don't report mouse tracking events.
+    pub fn on_mouse<F: Fn(MouseTrackingEvent) + 'static>(&self, _callback: F) {}
+
+    pub fn set_floating_panel(&self, value: bool) {
+        let mut state = self.state.lock().unwrap();
+
+        state.floating = value;
+        self.update_always_on_top(&state);
+    }
+
+    /// Does nothing, windows always receive mouse moved events.
+    pub fn set_accepts_mouse_moved_events(&self, _value: bool) {}
+
+    pub fn set_ignore_mouse_events(&self, value: bool) {
+        if let Err(error) = self.window.set_ignore_cursor_events(value) {
+            log_failure("set_ignore_mouse_events", error);
+        }
+    }
+
+    /// Windows are never hidden on deactivation, so only `false` is supported.
+    pub fn set_hides_on_deactivate(&self, value: bool) {
+        if value {
+            log_unsupported("set_hides_on_deactivate");
+        }
+    }
+
+    pub fn activate(&self) {
+        self.set_level(PanelLevel::Floating);
+        self.show();
+    }
+
+    /// Logs [`Error::Unsupported`], use `data-tauri-drag-region` instead.
+    pub fn set_moveable_by_window_background(&self, _value: bool) {
+        log_unsupported("set_moveable_by_window_background");
+    }
+
+    /// Windows always become key when clicked, so only `false` is supported.
+    pub fn set_becomes_key_only_if_needed(&self, value: bool) {
+        if value {
+            log_unsupported("set_becomes_key_only_if_needed");
+        }
+    }
+
+    /// Logs [`Error::Unsupported`].
+    pub fn set_works_when_modal(&self, _value: bool) {
+        log_unsupported("set_works_when_modal");
+    }
+
+    /// Logs [`Error::Unsupported`], use the `transparent` window option instead.
+    pub fn set_opaque(&self, _value: bool) {
+        log_unsupported("set_opaque");
+    }
+
+    pub fn set_has_shadow(&self, value: bool) {
+        if let Err(error) = self.window.set_shadow(value) {
+            log_failure("set_has_shadow", error);
+        }
+    }
+
+    /// Does nothing, closed windows are always released.
+    pub fn set_released_when_closed(&self, _value: bool) {}
+
+    /// Does nothing, windows have no delegate. Use [`FallbackPanel::on_event`] instead.
+    pub fn set_delegate<T>(&self, _delegate: T) {}
+
+    pub fn close(&self) {
+        if let Err(error) = self.window.close() {
+            log_failure("close", error);
+        }
+    }
+}
+
+/// Calls the event listeners of a panel, outside the lock so they can use the panel.
+fn emit(state: &Mutex<FallbackState>, event: PanelEvent) {
+    let listeners = state.lock().unwrap().listeners.clone();
+
+    for listener in listeners {
+        listener(event.clone());
+    }
+}
+
+/// Tauri doesn't report which screen has the key window, so
+/// [`ScreenTarget::MainScreen`] falls back to the primary screen.
+impl ScreenSource for FallbackPanel {
+    fn screens(&self) -> Vec<PanelScreen> {
+        self.window
+            .available_monitors()
+            .map(|monitors| monitors.iter().map(PanelScreen::from_monitor).collect())
+            .unwrap_or_default()
+    }
+
+    fn cursor_position(&self) -> Option<PanelPoint> {
+        let position = self.window.cursor_position().ok()?;
+
+        Some(screen::logical_point(position, &self.screens()))
+    }
+
+    fn main_screen(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Logs a failed window operation. Panel methods can't fail on macOS, so they don't here either.
+fn log_failure(operation: &str, error: tauri::Error) {
+    log::warn!("panel operation `{operation}` failed: {error}");
+}
+
+fn log_unsupported(operation: &'static str) {
+    log::warn!("{}", Error::Unsupported { operation });
+}
+
+impl PanelBackend for Arc<FallbackPanel> {
+    fn show(&self) {
+        FallbackPanel::show(self);
+    }
+
+    fn hide(&self) {
+        self.order_out(None);
+    }
+
+    fn close(&self) {
+        FallbackPanel::close(self);
+    }
+
+    fn is_visible(&self) -> bool {
+        FallbackPanel::is_visible(self)
+    }
+
+    fn make_key(&self) {
+        self.make_key_window();
+    }
+
+    fn resign_key(&self) {
+        self.resign_key_window();
+    }
+
+    fn is_key(&self) -> bool {
+        self.is_key_window()
+    }
+
+    fn set_level(&self, level: PanelLevel) {
+        FallbackPanel::set_level(self, level);
+    }
+
+    fn level(&self) -> PanelLevel {
+        FallbackPanel::level(self)
+    }
+
+    fn set_style_mask(&self, style_mask: PanelStyleMask) {
+        FallbackPanel::set_style_mask(self, style_mask);
+    }
+
+    fn style_mask(&self) -> PanelStyleMask {
+        FallbackPanel::style_mask(self)
+    }
+
+    fn set_alpha_value(&self, value: f64) {
+        FallbackPanel::set_alpha_value(self, value);
+    }
+
+    fn alpha_value(&self) -> f64 {
+        FallbackPanel::alpha_value(self)
+    }
+}
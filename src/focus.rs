@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// When a panel takes keyboard focus, i.e. becomes the key window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FocusPolicy {
+    /// Becomes key when shown and when the mouse enters it, resigns key when the mouse exits.
+    /// Requires the tracking area.
+    #[default]
+    KeyOnHover,
+    /// Only becomes key when clicked.
+    KeyOnClick,
+    /// Becomes key when shown, then keeps focus until another window takes it.
+    KeyOnShow,
+    /// Never made key by the plugin. Set
+    /// [`PanelCapabilities::can_become_key_window`](crate::PanelCapabilities::can_become_key_window)
+    /// to `false` to also refuse key status on click.
+    Never,
+}
+
+impl FocusPolicy {
+    pub(crate) fn key_on_show(self) -> bool {
+        matches!(self, FocusPolicy::KeyOnHover | FocusPolicy::KeyOnShow)
+    }
+
+    // Only panels track hovering, windows on other platforms don't
+    #[cfg(target_os = "macos")]
+    pub(crate) fn key_on_hover(self) -> bool {
+        self == FocusPolicy::KeyOnHover
+    }
+}
+
+/// A [`FocusPolicy`] with the delays applied to [`FocusPolicy::KeyOnHover`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FocusOptions {
+    pub policy: FocusPolicy,
+    /// How long after the mouse enters the panel it becomes key. Defaults to none.
+    pub enter_delay: Duration,
+    /// How long after the mouse exits the panel it resigns key, so hover effects can update.
+    /// Defaults to 20ms.
+    pub exit_delay: Duration,
+}
+
+impl Default for FocusOptions {
+    fn default() -> Self {
+        Self {
+            policy: FocusPolicy::default(),
+            enter_delay: Duration::ZERO,
+            exit_delay: Duration::from_millis(20),
+        }
+    }
+}
+
+impl From<FocusPolicy> for FocusOptions {
+    fn from(policy: FocusPolicy) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,290 @@
+#[cfg(target_os = "macos")]
+use cocoa::foundation::{NSPoint, NSRect, NSSize};
+use serde::{Deserialize, Serialize};
+use tauri::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
+
+/// A point in logical (point) coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PanelPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A rectangle in logical (point) coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PanelRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[cfg(target_os = "macos")]
+impl From<NSPoint> for PanelPoint {
+    fn from(point: NSPoint) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl From<PanelPoint> for NSPoint {
+    fn from(point: PanelPoint) -> Self {
+        NSPoint::new(point.x, point.y)
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl From<NSRect> for PanelRect {
+    fn from(rect: NSRect) -> Self {
+        Self {
+            x: rect.origin.x,
+            y: rect.origin.y,
+            width: rect.size.width,
+            height: rect.size.height,
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl From<PanelRect> for NSRect {
+    fn from(rect: PanelRect) -> Self {
+        NSRect::new(
+            NSPoint::new(rect.x, rect.y),
+            NSSize::new(rect.width, rect.height),
+        )
+    }
+}
+
+impl From<LogicalPosition<f64>> for PanelPoint {
+    fn from(position: LogicalPosition<f64>) -> Self {
+        Self {
+            x: position.x,
+            y: position.y,
+        }
+    }
+}
+
+impl From<PanelPoint> for LogicalPosition<f64> {
+    fn from(point: PanelPoint) -> Self {
+        LogicalPosition::new(point.x, point.y)
+    }
+}
+
+impl From<PanelPoint> for Position {
+    fn from(point: PanelPoint) -> Self {
+        Position::Logical(point.into())
+    }
+}
+
+impl PanelPoint {
+    pub fn to_physical(&self, scale_factor: f64) -> PhysicalPosition<f64> {
+        LogicalPosition::<f64>::from(*self).to_physical(scale_factor)
+    }
+
+    /// Converts a point from AppKit screen coordinates, with the origin at the bottom-left of the
+    /// primary screen, to Tauri's, with the origin at its top-left.
+    pub fn to_top_left(&self, primary_screen_height: f64) -> Self {
+        Self {
+            x: self.x,
+            y: primary_screen_height - self.y,
+        }
+    }
+
+    /// The inverse of [`PanelPoint::to_top_left`].
+    pub fn to_bottom_left(&self, primary_screen_height: f64) -> Self {
+        self.to_top_left(primary_screen_height)
+    }
+}
+
+impl PanelRect {
+    pub fn new(origin: PanelPoint, size: LogicalSize<f64>) -> Self {
+        Self {
+            x: origin.x,
+            y: origin.y,
+            width: size.width,
+            height: size.height,
+        }
+    }
+
+    /// Builds a rect from the physical position and size Tauri reports for windows.
+    pub fn from_physical(
+        position: PhysicalPosition<i32>,
+        size: PhysicalSize<u32>,
+        scale_factor: f64,
+    ) -> Self {
+        Self::new(
+            position.to_logical::<f64>(scale_factor).into(),
+            size.to_logical(scale_factor),
+        )
+    }
+
+    pub fn origin(&self) -> PanelPoint {
+        PanelPoint {
+            x: self.x,
+            y: self.y,
+        }
+    }
+
+    pub fn size(&self) -> LogicalSize<f64> {
+        LogicalSize::new(self.width, self.height)
+    }
+
+    pub fn center(&self) -> PanelPoint {
+        PanelPoint {
+            x: self.x + self.width / 2.0,
+            y: self.y + self.height / 2.0,
+        }
+    }
+
+    pub fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    /// Returns the overlapping part of both rects, if they overlap.
+    pub fn intersection(&self, other: &PanelRect) -> Option<PanelRect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.width).min(other.x + other.width);
+        let bottom = (self.y + self.height).min(other.y + other.height);
+
+        (right > x && bottom > y).then_some(PanelRect {
+            x,
+            y,
+            width: right - x,
+            height: bottom - y,
+        })
+    }
+
+    /// Whether `point` lies within the rect, including its top and left edges only.
+    pub fn contains(&self, point: PanelPoint) -> bool {
+        point.x >= self.x
+            && point.x < self.x + self.width
+            && point.y >= self.y
+            && point.y < self.y + self.height
+    }
+
+    /// Converts a rect from AppKit screen coordinates, where `y` is its bottom edge measured up
+    /// from the bottom of the primary screen, to Tauri's, where `y` is its top edge measured down
+    /// from the top of the primary screen.
+    pub fn to_top_left(&self, primary_screen_height: f64) -> Self {
+        Self {
+            y: primary_screen_height - self.y - self.height,
+            ..*self
+        }
+    }
+
+    /// The inverse of [`PanelRect::to_top_left`].
+    pub fn to_bottom_left(&self, primary_screen_height: f64) -> Self {
+        self.to_top_left(primary_screen_height)
+    }
+}
+
+impl From<PanelRect> for Size {
+    fn from(rect: PanelRect) -> Self {
+        Size::Logical(rect.size())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIMARY_SCREEN_HEIGHT: f64 = 1080.0;
+
+    #[test]
+    fn point_to_top_left() {
+        let point = PanelPoint { x: 100.0, y: 80.0 };
+
+        assert_eq!(
+            point.to_top_left(PRIMARY_SCREEN_HEIGHT),
+            PanelPoint {
+                x: 100.0,
+                y: 1000.0
+            }
+        );
+    }
+
+    #[test]
+    fn rect_to_top_left_uses_its_top_edge() {
+        // A 300x200 panel 80 points above the bottom of the primary screen
+        let rect = PanelRect {
+            x: 100.0,
+            y: 80.0,
+            width: 300.0,
+            height: 200.0,
+        };
+
+        assert_eq!(
+            rect.to_top_left(PRIMARY_SCREEN_HEIGHT),
+            PanelRect {
+                x: 100.0,
+                y: 800.0,
+                width: 300.0,
+                height: 200.0,
+            }
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        let point = PanelPoint { x: 12.5, y: 640.0 };
+        let rect = PanelRect {
+            x: 12.5,
+            y: 640.0,
+            width: 420.0,
+            height: 310.0,
+        };
+
+        assert_eq!(
+            point
+                .to_top_left(PRIMARY_SCREEN_HEIGHT)
+                .to_bottom_left(PRIMARY_SCREEN_HEIGHT),
+            point
+        );
+        assert_eq!(
+            rect.to_top_left(PRIMARY_SCREEN_HEIGHT)
+                .to_bottom_left(PRIMARY_SCREEN_HEIGHT),
+            rect
+        );
+        assert_eq!(
+            rect.to_bottom_left(PRIMARY_SCREEN_HEIGHT)
+                .to_top_left(PRIMARY_SCREEN_HEIGHT),
+            rect
+        );
+    }
+
+    #[test]
+    fn converts_rects_on_other_screens() {
+        // A 1440x900 screen left of and above the primary one, in AppKit coordinates
+        let secondary = PanelRect {
+            x: -1440.0,
+            y: 1080.0,
+            width: 1440.0,
+            height: 900.0,
+        };
+        // And one below it
+        let below = PanelRect {
+            x: 0.0,
+            y: -900.0,
+            width: 1440.0,
+            height: 900.0,
+        };
+
+        let top_left = secondary.to_top_left(PRIMARY_SCREEN_HEIGHT);
+        assert_eq!(
+            top_left.origin(),
+            PanelPoint {
+                x: -1440.0,
+                y: -900.0
+            }
+        );
+        assert_eq!(top_left.to_bottom_left(PRIMARY_SCREEN_HEIGHT), secondary);
+
+        let top_left = below.to_top_left(PRIMARY_SCREEN_HEIGHT);
+        assert_eq!(top_left.origin(), PanelPoint { x: 0.0, y: 1080.0 });
+        assert_eq!(top_left.to_bottom_left(PRIMARY_SCREEN_HEIGHT), below);
+    }
+}
@@ -0,0 +1,234 @@
+use std::sync::mpsc;
+
+use tauri::{AppHandle, Runtime};
+
+use crate::{Animation, Error, MainThreadMarker, ManagerExt, Panel, PanelBackend};
+#[cfg(target_os = "macos")]
+use crate::{PanelLevel, PanelStyleMask, RawNSPanel};
+
+/// A `Send + Sync` handle to a panel, usable from any thread.
+///
+/// Every operation is dispatched to the main thread with [`AppHandle::run_on_main_thread`],
+/// where the panel is looked up again, so the handle outlives the panel safely and fails with
+/// [`Error::PanelNotFound`] once it is gone.
+///
+/// ```ignore
+/// #[tauri::command]
+/// async fn show_panel(app: tauri::AppHandle) -> Result<(), tauri_nspanel::Error> {
+///     let handle = app.get_panel_handle("main")?;
+///
+///     handle.run_async(|panel| panel.show()).await
+/// }
+/// ```
+pub struct PanelHandle<R: Runtime> {
+    app: AppHandle<R>,
+    label: String,
+}
+
+impl<R: Runtime> Clone for PanelHandle<R> {
+    fn clone(&self) -> Self {
+        Self {
+            app: self.app.clone(),
+            label: self.label.clone(),
+        }
+    }
+}
+
+impl<R: Runtime> PanelHandle<R> {
+    pub(crate) fn new(app: AppHandle<R>, label: String) -> Self {
+        Self { app, label }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the panel itself, which may only be used on the main thread.
+    pub fn panel(&self, _marker: MainThreadMarker) -> Result<Panel, Error> {
+        self.app.get_webview_panel(&self.label)
+    }
+
+    /// Runs `f` with the panel on the main thread and blocks until it returns.
+    ///
+    /// Called on the main thread, `f` runs immediately instead of being dispatched, so this never
+    /// deadlocks the event loop. `T` must be `Send`, so the panel can't escape the main thread.
+    pub fn run<T, F>(&self, f: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Panel) -> T + Send + 'static,
+    {
+        if let Some(marker) = MainThreadMarker::new() {
+            return Ok(f(&self.panel(marker)?));
+        }
+
+        let (tx, rx) = mpsc::sync_channel(1);
+
+        self.dispatch(f, move |result| {
+            let _ = tx.send(result);
+        })?;
+
+        rx.recv().map_err(|_| Error::MainThreadUnavailable)?
+    }
+
+    /// Runs `f` with the panel on the main thread and resolves once it returns.
+    ///
+    /// Don't block the main thread on the returned future, `f` could never run.
+    pub async fn run_async<T, F>(&self, f: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Panel) -> T + Send + 'static,
+    {
+        let (tx, mut rx) = tauri::async_runtime::channel(1);
+
+        self.dispatch(f, move |result| {
+            let _ = tx.try_send(result);
+        })?;
+
+        rx.recv().await.ok_or(Error::MainThreadUnavailable)?
+    }
+
+    /// Runs `f` with the panel's [`PanelBackend`] on the main thread, see [`PanelHandle::run`].
+    fn run_backend<T, F>(&self, f: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&(dyn PanelBackend + 'static)) -> T + Send + 'static,
+    {
+        let app = self.app.clone();
+        let label = self.label.clone();
+
+        self.run(move |_| app.get_panel_backend(&label).map(|backend| f(&*backend)))?
+    }
+
+    /// Runs `f` like [`PanelHandle::run`], returning `fallback` if the panel is gone, as a
+    /// [`PanelBackend`] can't report failures.
+    #[cfg(target_os = "macos")]
+    fn run_or_warn<T, F>(&self, fallback: T, f: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce(&Panel) -> T + Send + 'static,
+    {
+        self.run(f).unwrap_or_else(|error| {
+            log::warn!("failed to reach panel `{}`: {error}", self.label);
+            fallback
+        })
+    }
+
+    fn dispatch<T, F, S>(&self, f: F, send: S) -> Result<(), Error>
+    where
+        F: FnOnce(&Panel) -> T + Send + 'static,
+        S: FnOnce(Result<T, Error>) + Send + 'static,
+    {
+        let app = self.app.clone();
+        let label = self.label.clone();
+
+        self.app
+            .run_on_main_thread(move || send(app.get_webview_panel(&label).map(|panel| f(&panel))))
+            .map_err(|_| Error::MainThreadUnavailable)
+    }
+
+    pub fn show(&self) -> Result<(), Error> {
+        self.run_backend(PanelBackend::show)
+    }
+
+    pub fn hide(&self) -> Result<(), Error> {
+        self.run_backend(PanelBackend::hide)
+    }
+
+    /// Hides the panel if it is visible and shows it otherwise, returning whether it is visible.
+    pub fn toggle(&self) -> Result<bool, Error> {
+        self.run_backend(PanelBackend::toggle)
+    }
+
+    /// Closes the panel and releases it.
+    pub fn close(&self) -> Result<(), Error> {
+        self.run_backend(PanelBackend::close)
+    }
+
+    pub fn is_visible(&self) -> Result<bool, Error> {
+        self.run_backend(PanelBackend::is_visible)
+    }
+
+    /// Shows the panel with `animation` and resolves once it finished or another animation
+    /// interrupted it, see `show_animated` on the panel.
+    pub async fn show_animated(&self, animation: Animation) -> Result<(), Error> {
+        let completed = self
+            .run_async(move |panel| panel.show_animated_async(animation))
+            .await?;
+
+        completed.await;
+
+        Ok(())
+    }
+
+    /// Hides the panel with `animation`, see [`PanelHandle::show_animated`].
+    pub async fn hide_animated(&self, animation: Animation) -> Result<(), Error> {
+        let completed = self
+            .run_async(move |panel| panel.hide_animated_async(animation))
+            .await?;
+
+        completed.await;
+
+        Ok(())
+    }
+}
+
+/// The backend of converted panels on macOS. Each call runs on the main thread and blocks until
+/// it returns, like [`PanelHandle::run`], so the backend can be used from any thread. Calls made
+/// after the panel is gone log a warning and return a default value.
+#[cfg(target_os = "macos")]
+impl<R: Runtime> PanelBackend for PanelHandle<R> {
+    fn show(&self) {
+        self.run_or_warn((), |panel| RawNSPanel::show(panel));
+    }
+
+    fn hide(&self) {
+        self.run_or_warn((), |panel| panel.order_out(None));
+    }
+
+    fn close(&self) {
+        self.run_or_warn((), |panel| {
+            panel.set_released_when_closed(true);
+            panel.close();
+        });
+    }
+
+    fn is_visible(&self) -> bool {
+        self.run_or_warn(false, |panel| panel.is_visible())
+    }
+
+    fn make_key(&self) {
+        self.run_or_warn((), |panel| panel.make_key_window());
+    }
+
+    fn resign_key(&self) {
+        self.run_or_warn((), |panel| panel.resign_key_window());
+    }
+
+    fn is_key(&self) -> bool {
+        self.run_or_warn(false, |panel| panel.is_key_window())
+    }
+
+    fn set_level(&self, level: PanelLevel) {
+        self.run_or_warn((), move |panel| panel.set_level(level));
+    }
+
+    fn level(&self) -> PanelLevel {
+        self.run_or_warn(PanelLevel::default(), |panel| panel.level())
+    }
+
+    fn set_style_mask(&self, style_mask: PanelStyleMask) {
+        self.run_or_warn((), move |panel| panel.set_style_mask(style_mask));
+    }
+
+    fn style_mask(&self) -> PanelStyleMask {
+        self.run_or_warn(PanelStyleMask::default(), |panel| panel.style_mask())
+    }
+
+    fn set_alpha_value(&self, value: f64) {
+        self.run_or_warn((), move |panel| panel.set_alpha_value(value));
+    }
+
+    fn alpha_value(&self) -> f64 {
+        self.run_or_warn(1.0, |panel| panel.alpha_value())
+    }
+}
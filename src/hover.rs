@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+/// A focus change triggered by the mouse entering or exiting a panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HoverAction {
+    MakeKey,
+    ResignKey,
+}
+
+/// When to run a scheduled [`HoverAction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Schedule {
+    /// Run it right away.
+    Now(HoverAction),
+    /// Call [`HoverScheduler::fire`] with `generation` once `delay` has elapsed.
+    Later { generation: u64, delay: Duration },
+}
+
+/// Timing and cancellation of the hover focus changes of a panel, independent of any timer.
+///
+/// At most one action is pending. Scheduling another one, e.g. making the panel key because the
+/// mouse re-entered it, cancels the pending one. Each scheduled action gets a new generation,
+/// and firing a stale generation does nothing.
+#[derive(Debug, Default)]
+pub(crate) struct HoverScheduler {
+    generation: u64,
+    pending: Option<(u64, HoverAction)>,
+}
+
+impl HoverScheduler {
+    pub(crate) fn schedule(&mut self, action: HoverAction, delay: Duration) -> Schedule {
+        self.generation = self.generation.wrapping_add(1);
+
+        if delay.is_zero() {
+            self.pending = None;
+
+            return Schedule::Now(action);
+        }
+
+        self.pending = Some((self.generation, action));
+
+        Schedule::Later {
+            generation: self.generation,
+            delay,
+        }
+    }
+
+    /// Returns the action scheduled as `generation` if it is still pending.
+    pub(crate) fn fire(&mut self, generation: u64) -> Option<HoverAction> {
+        match self.pending {
+            Some((pending, action)) if pending == generation => {
+                self.pending = None;
+
+                Some(action)
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn cancel(&mut self) {
+        self.pending = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DELAY: Duration = Duration::from_millis(150);
+
+    #[test]
+    fn fires_pending_action_once() {
+        let mut scheduler = HoverScheduler::default();
+
+        let Schedule::Later { generation, delay } = scheduler.schedule(HoverAction::MakeKey, DELAY)
+        else {
+            panic!("expected a delayed action");
+        };
+
+        assert_eq!(delay, DELAY);
+        assert_eq!(scheduler.fire(generation), Some(HoverAction::MakeKey));
+        assert_eq!(scheduler.fire(generation), None);
+    }
+
+    #[test]
+    fn generation_increments_on_re_enter() {
+        let mut scheduler = HoverScheduler::default();
+
+        let first = scheduler.schedule(HoverAction::MakeKey, DELAY);
+        scheduler.schedule(HoverAction::ResignKey, DELAY);
+        let second = scheduler.schedule(HoverAction::MakeKey, DELAY);
+
+        let (
+            Schedule::Later {
+                generation: first, ..
+            },
+            Schedule::Later {
+                generation: second, ..
+            },
+        ) = (first, second)
+        else {
+            panic!("expected delayed actions");
+        };
+
+        assert_eq!(second, first + 2);
+        assert_eq!(scheduler.fire(first), None);
+        assert_eq!(scheduler.fire(second), Some(HoverAction::MakeKey));
+    }
+
+    #[test]
+    fn ignores_stale_generation_after_exit() {
+        let mut scheduler = HoverScheduler::default();
+
+        let Schedule::Later { generation, .. } = scheduler.schedule(HoverAction::MakeKey, DELAY)
+        else {
+            panic!("expected a delayed action");
+        };
+
+        // The mouse exits before the delay elapsed
+        scheduler.cancel();
+
+        assert_eq!(scheduler.fire(generation), None);
+    }
+
+    #[test]
+    fn zero_delay_runs_now() {
+        let mut scheduler = HoverScheduler::default();
+
+        let Schedule::Later { generation, .. } = scheduler.schedule(HoverAction::MakeKey, DELAY)
+        else {
+            panic!("expected a delayed action");
+        };
+
+        assert_eq!(
+            scheduler.schedule(HoverAction::ResignKey, Duration::ZERO),
+            Schedule::Now(HoverAction::ResignKey)
+        );
+        // Running an action right away cancels the pending one
+        assert_eq!(scheduler.fire(generation), None);
+    }
+}
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+
+/// Window level of a panel.
+///
+/// The named levels mirror AppKit's `NS*WindowLevel` constants. Use [`PanelLevel::Custom`]
+/// for an arbitrary raw level and [`PanelLevel::Relative`] to stack a panel just above
+/// (or below) one of the named levels.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PanelLevel {
+    /// `NSNormalWindowLevel`
+    Normal,
+    /// `NSFloatingWindowLevel`
+    #[default]
+    Floating,
+    /// `NSModalPanelWindowLevel`
+    ModalPanel,
+    /// `NSMainMenuWindowLevel`
+    MainMenu,
+    /// `NSStatusWindowLevel`
+    Status,
+    /// `NSPopUpMenuWindowLevel`
+    PopUpMenu,
+    /// `NSScreenSaverWindowLevel`
+    ScreenSaver,
+    /// A raw window level.
+    Custom(i32),
+    /// A level offset from another level, e.g. `Relative(Box::new(PanelLevel::Status), 1)`.
+    Relative(Box<PanelLevel>, i32),
+}
+
+impl PanelLevel {
+    /// Returns the raw AppKit window level.
+    pub fn value(&self) -> i32 {
+        match self {
+            PanelLevel::Normal => 0,
+            PanelLevel::Floating => 3,
+            PanelLevel::ModalPanel => 8,
+            PanelLevel::MainMenu => 24,
+            PanelLevel::Status => 25,
+            PanelLevel::PopUpMenu => 101,
+            PanelLevel::ScreenSaver => 1000,
+            PanelLevel::Custom(value) => *value,
+            PanelLevel::Relative(base, offset) => base.value() + offset,
+        }
+    }
+}
+
+impl From<i32> for PanelLevel {
+    /// Maps a raw level back to its named level, falling back to [`PanelLevel::Custom`].
+    fn from(value: i32) -> Self {
+        match value {
+            0 => PanelLevel::Normal,
+            3 => PanelLevel::Floating,
+            8 => PanelLevel::ModalPanel,
+            24 => PanelLevel::MainMenu,
+            25 => PanelLevel::Status,
+            101 => PanelLevel::PopUpMenu,
+            1000 => PanelLevel::ScreenSaver,
+            value => PanelLevel::Custom(value),
+        }
+    }
+}
+
+impl From<PanelLevel> for i32 {
+    fn from(level: PanelLevel) -> Self {
+        level.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMED: [(PanelLevel, i32); 7] = [
+        (PanelLevel::Normal, 0),
+        (PanelLevel::Floating, 3),
+        (PanelLevel::ModalPanel, 8),
+        (PanelLevel::MainMenu, 24),
+        (PanelLevel::Status, 25),
+        (PanelLevel::PopUpMenu, 101),
+        (PanelLevel::ScreenSaver, 1000),
+    ];
+
+    #[test]
+    fn named_levels_match_appkit() {
+        for (level, value) in NAMED {
+            assert_eq!(level.value(), value, "{level:?}");
+        }
+    }
+
+    #[test]
+    fn round_trips_raw_levels() {
+        for (level, value) in NAMED {
+            assert_eq!(PanelLevel::from(value), level);
+            assert_eq!(i32::from(level), value);
+        }
+
+        assert_eq!(PanelLevel::from(42), PanelLevel::Custom(42));
+        assert_eq!(PanelLevel::from(-1), PanelLevel::Custom(-1));
+        assert_eq!(i32::from(PanelLevel::Custom(42)), 42);
+    }
+
+    #[test]
+    fn relative_levels_offset_their_base() {
+        let level = PanelLevel::Relative(Box::new(PanelLevel::MainMenu), 1);
+
+        assert_eq!(level.value(), 25);
+        // Raw levels map back to the named level, not the relative one
+        assert_eq!(PanelLevel::from(level.value()), PanelLevel::Status);
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let levels = [
+            (PanelLevel::Floating, serde_json::json!("floating")),
+            (PanelLevel::PopUpMenu, serde_json::json!("popUpMenu")),
+            (PanelLevel::Custom(42), serde_json::json!({ "custom": 42 })),
+            (
+                PanelLevel::Relative(Box::new(PanelLevel::Status), -1),
+                serde_json::json!({ "relative": ["status", -1] }),
+            ),
+        ];
+
+        for (level, json) in levels {
+            assert_eq!(serde_json::to_value(&level).unwrap(), json);
+            assert_eq!(serde_json::from_value::<PanelLevel>(json).unwrap(), level);
+        }
+    }
+}
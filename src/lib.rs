@@ -1,28 +1,132 @@
+mod anchor;
+mod animation;
+mod backend;
+mod builder;
+mod capabilities;
+mod collection_behavior;
+mod commands;
+mod config;
+mod dismiss;
+mod error;
+mod event;
+#[cfg(not(target_os = "macos"))]
+mod fallback;
+mod focus;
+mod geometry;
+mod handle;
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+mod hover;
+mod level;
 mod macros;
+mod main_thread;
+#[cfg(feature = "test")]
+pub mod mock;
+#[cfg(target_os = "macos")]
+pub mod monitor;
+mod mouse;
+#[cfg(target_os = "macos")]
 pub mod raw_nspanel;
+mod screen;
+mod style_mask;
+mod tracking;
 
-use std::{collections::HashMap, sync::Mutex};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, MutexGuard},
+};
 
+#[cfg(target_os = "macos")]
 use cocoa::base::id;
+#[cfg(target_os = "macos")]
+use main_thread::MainThreadBound;
+#[cfg(target_os = "macos")]
 use objc_id::ShareId;
+#[cfg(target_os = "macos")]
 use raw_nspanel::RawNSPanel;
 use tauri::{
     plugin::{Builder, TauriPlugin},
-    Manager, Runtime, WebviewWindow,
+    Manager, RunEvent, Runtime, WebviewWindow, WindowEvent,
 };
 
+#[cfg(target_os = "macos")]
 pub extern crate block;
+#[cfg(target_os = "macos")]
 pub extern crate cocoa;
+#[cfg(target_os = "macos")]
 pub extern crate objc;
+#[cfg(target_os = "macos")]
 pub extern crate objc_foundation;
+#[cfg(target_os = "macos")]
 pub extern crate objc_id;
 pub extern crate tauri;
 
+pub use anchor::{anchor_position, AnchorAlignment, AnchorOptions};
+pub use animation::{Animation, AnimationDirection, Easing, Edge, Keyframe, Transition};
+pub use backend::PanelBackend;
+pub use builder::PanelBuilder;
+pub use capabilities::PanelCapabilities;
+pub use collection_behavior::PanelCollectionBehavior;
+pub use config::{Config, PanelConfig};
+pub use dismiss::DismissPolicy;
+pub use error::Error;
+pub use event::{PanelEvent, PanelEventPayload, PanelEventTarget};
+#[cfg(not(target_os = "macos"))]
+pub use fallback::FallbackPanel;
+pub use focus::{FocusOptions, FocusPolicy};
+pub use geometry::{PanelPoint, PanelRect};
+pub use handle::PanelHandle;
+pub use level::PanelLevel;
+pub use main_thread::MainThreadMarker;
+pub use mouse::{MouseTrackingEvent, MouseTrackingEventPayload};
+pub use screen::{move_between_screens, PanelScreen, ScreenTarget};
+pub use style_mask::PanelStyleMask;
+pub use tracking::{TrackingArea, TrackingAreaId, TrackingOptions, TrackingRect};
+
+#[cfg(target_os = "macos")]
 pub type Panel = ShareId<RawNSPanel>;
 
+/// On platforms without `NSPanel`, panels fall back to the portable [`WebviewWindow`] API.
+#[cfg(not(target_os = "macos"))]
+pub type Panel = Arc<FallbackPanel>;
+
+/// A panel kept in the [`Store`]. `NSPanel`s aren't `Send`, so the store only hands them out on
+/// the main thread.
+#[cfg(target_os = "macos")]
+type StoredPanel = MainThreadBound<Panel>;
+
+#[cfg(not(target_os = "macos"))]
+type StoredPanel = Panel;
+
 #[derive(Default)]
 pub struct Store {
-    panels: HashMap<String, ShareId<RawNSPanel>>,
+    panels: HashMap<String, StoredPanel>,
+    backends: HashMap<String, Arc<dyn PanelBackend>>,
+    declared: HashMap<String, PanelConfig>,
+    /// Labels of panels whose window was destroyed, to report stale lookups.
+    closed: HashSet<String>,
+}
+
+impl Store {
+    fn panel(&self, label: &str) -> Result<Panel, Error> {
+        let panel = self.panels.get(label).ok_or_else(|| self.missing(label))?;
+
+        #[cfg(target_os = "macos")]
+        let panel = panel.get(MainThreadMarker::new().ok_or(Error::NotOnMainThread)?);
+
+        Ok(panel.clone())
+    }
+
+    fn missing(&self, label: &str) -> Error {
+        if self.closed.contains(label) {
+            Error::PanelClosed {
+                label: label.into(),
+            }
+        } else {
+            Error::PanelNotFound {
+                label: label.into(),
+            }
+        }
+    }
 }
 
 pub struct WebviewPanelManager(pub Mutex<Store>);
@@ -33,80 +137,354 @@ impl Default for WebviewPanelManager {
     }
 }
 
-pub trait ManagerExt<R: Runtime> {
-    fn get_webview_panel(&self, label: &str) -> Result<ShareId<RawNSPanel>, Error>;
+fn lock_store<R: Runtime, M: Manager<R>>(manager: &M) -> Result<MutexGuard<'_, Store>, Error> {
+    let state = manager
+        .try_state::<self::WebviewPanelManager>()
+        .ok_or(Error::PluginNotInitialized)?;
+
+    state.inner().0.lock().map_err(|_| Error::StorePoisoned)
 }
 
-#[derive(Debug)]
-pub enum Error {
-    PanelNotFound,
+pub trait ManagerExt<R: Runtime> {
+    /// Returns the panel itself. AppKit may only be used on the main thread, so this fails with
+    /// [`Error::NotOnMainThread`] elsewhere, use [`ManagerExt::get_panel_handle`] instead.
+    ///
+    /// Only windows converted with [`WebviewWindowExt::to_panel`] are panels. A backend
+    /// registered with [`ManagerExt::register_panel_backend`] fails with
+    /// [`Error::PanelNotFound`] here and in [`ManagerExt::get_panel_handle`], use
+    /// [`ManagerExt::get_panel_backend`] to reach it.
+    fn get_webview_panel(&self, label: &str) -> Result<Panel, Error>;
+
+    /// Returns a [`PanelHandle`] to the panel, which can be used from any thread. Like
+    /// [`ManagerExt::get_webview_panel`], it can't reach registered backends.
+    fn get_panel_handle(&self, label: &str) -> Result<PanelHandle<R>, Error>;
+
+    /// Returns the [`PanelBackend`] registered for `label`, either by converting a window with
+    /// [`WebviewWindowExt::to_panel`] or with [`ManagerExt::register_panel_backend`].
+    ///
+    /// It can be used from any thread. On macOS, the backend of a converted panel runs each call
+    /// on the main thread and blocks until it returns, so don't call it while blocking the main
+    /// thread.
+    fn get_panel_backend(&self, label: &str) -> Result<Arc<dyn PanelBackend>, Error>;
+
+    /// Registers `backend` under `label`, e.g. a [`MockPanel`](crate::mock::MockPanel) in tests.
+    /// It is then used by the plugin commands, but isn't returned by
+    /// [`ManagerExt::get_webview_panel`].
+    fn register_panel_backend(
+        &self,
+        label: &str,
+        backend: Arc<dyn PanelBackend>,
+    ) -> Result<(), Error>;
+
+    /// Unregisters the panel or backend `label`, which is done automatically when a panel's
+    /// window is destroyed. The window stays open, but on macOS the panel stops animating and
+    /// loses its tracking areas, event observers and dismiss policy observers.
+    fn remove_webview_panel(&self, label: &str) -> Result<(), Error>;
 }
 
 impl<R: Runtime, T: Manager<R>> ManagerExt<R> for T {
-    fn get_webview_panel(&self, label: &str) -> Result<ShareId<RawNSPanel>, Error> {
-        let manager = self.state::<self::WebviewPanelManager>();
-        let manager = manager.0.lock().unwrap();
+    fn get_webview_panel(&self, label: &str) -> Result<Panel, Error> {
+        let store = lock_store(self)?;
+
+        MainThreadMarker::new().ok_or(Error::NotOnMainThread)?;
+
+        store.panel(label)
+    }
+
+    fn get_panel_handle(&self, label: &str) -> Result<PanelHandle<R>, Error> {
+        let store = lock_store(self)?;
+
+        if !store.panels.contains_key(label) {
+            return Err(store.missing(label));
+        }
+
+        Ok(PanelHandle::new(self.app_handle().clone(), label.into()))
+    }
+
+    fn get_panel_backend(&self, label: &str) -> Result<Arc<dyn PanelBackend>, Error> {
+        let store = lock_store(self)?;
+
+        match store.backends.get(label) {
+            Some(backend) => Ok(backend.clone()),
+            None => Err(store.missing(label)),
+        }
+    }
+
+    fn register_panel_backend(
+        &self,
+        label: &str,
+        backend: Arc<dyn PanelBackend>,
+    ) -> Result<(), Error> {
+        let mut store = lock_store(self)?;
+
+        store.closed.remove(label);
+        store.backends.insert(label.into(), backend);
+
+        Ok(())
+    }
+
+    fn remove_webview_panel(&self, label: &str) -> Result<(), Error> {
+        let mut store = lock_store(self)?;
+
+        let panel = store.panels.remove(label);
+        let backend = store.backends.remove(label);
 
-        match manager.panels.get(label) {
-            Some(panel) => Ok(panel.clone()),
-            None => Err(Error::PanelNotFound),
+        if panel.is_none() && backend.is_none() {
+            return Err(store.missing(label));
         }
+
+        drop(store);
+
+        let release = move || {
+            // The window keeps the panel alive, drop what the plugin registered for it
+            #[cfg(target_os = "macos")]
+            if let (Some(panel), Some(marker)) = (&panel, MainThreadMarker::new()) {
+                panel.get(marker).tear_down();
+            }
+
+            drop((panel, backend));
+        };
+
+        // Panels are released on the main thread
+        match MainThreadMarker::new() {
+            Some(_) => release(),
+            None => {
+                let _ = self.app_handle().run_on_main_thread(release);
+            }
+        }
+
+        Ok(())
     }
 }
 
+/// What [`WebviewWindowExt::to_panel_with_config`] does with a window that is already a panel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConversionPolicy {
+    /// Return the existing panel after applying the level, style mask, collection behavior,
+    /// floating and `hides_on_deactivate` settings of the new config that aren't `None`, and its
+    /// focus options, screen target and dismiss policy. The capabilities, tracking area and event
+    /// forwarding can only be set when the window is first converted and are ignored.
+    #[default]
+    ReuseExisting,
+    /// Fail with [`Error::AlreadyConverted`].
+    Fail,
+}
+
 pub struct WebviewPanelConfig {
+    #[cfg(target_os = "macos")]
     pub delegate: Option<id>,
     pub with_tracking_area: bool,
+    pub tracking_options: TrackingOptions,
+    pub tracking_rect: TrackingRect,
+    /// Emits every [`PanelEvent`] as a `nspanel://<event>` Tauri event, e.g. `nspanel://did-become-key`,
+    /// with a [`PanelEventPayload`].
+    pub forward_events: Option<PanelEventTarget>,
+    /// Emits every [`MouseTrackingEvent`] as a Tauri event, e.g. `nspanel://mouse-moved`, with a
+    /// [`MouseTrackingEventPayload`]. Requires the tracking area.
+    pub forward_mouse_events: Option<PanelEventTarget>,
+    /// Defaults to [`PanelLevel::Floating`].
+    pub level: Option<PanelLevel>,
+    pub style_mask: Option<PanelStyleMask>,
+    pub collection_behavior: Option<PanelCollectionBehavior>,
+    pub floating: Option<bool>,
+    pub hides_on_deactivate: Option<bool>,
+    pub capabilities: PanelCapabilities,
+    pub focus: FocusOptions,
+    /// The screen the panel is moved to whenever it is shown.
+    pub screen_target: Option<ScreenTarget>,
+    /// When the panel hides itself, nothing by default.
+    pub dismiss_policy: DismissPolicy,
+    pub conversion_policy: ConversionPolicy,
 }
 
 impl Default for WebviewPanelConfig {
     fn default() -> Self {
         Self {
+            #[cfg(target_os = "macos")]
             delegate: None,
             with_tracking_area: true,
+            tracking_options: TrackingOptions::default(),
+            tracking_rect: TrackingRect::default(),
+            forward_events: None,
+            forward_mouse_events: None,
+            level: None,
+            style_mask: None,
+            collection_behavior: None,
+            floating: None,
+            hides_on_deactivate: None,
+            capabilities: PanelCapabilities::default(),
+            focus: FocusOptions::default(),
+            screen_target: None,
+            dismiss_policy: DismissPolicy::empty(),
+            conversion_policy: ConversionPolicy::default(),
         }
     }
 }
 
 pub trait WebviewWindowExt<R: Runtime> {
-    fn to_panel(&self) -> tauri::Result<ShareId<RawNSPanel>>;
-    fn to_panel_with_config(
-        &self,
-        config: WebviewPanelConfig,
-    ) -> tauri::Result<ShareId<RawNSPanel>>;
+    fn to_panel(&self) -> Result<Panel, Error>;
+    /// Converting a window that is already a panel follows
+    /// [`WebviewPanelConfig::conversion_policy`] instead of converting it again.
+    fn to_panel_with_config(&self, config: WebviewPanelConfig) -> Result<Panel, Error>;
 }
 
 impl<R: Runtime> WebviewWindowExt<R> for WebviewWindow<R> {
-    fn to_panel(&self) -> tauri::Result<ShareId<RawNSPanel>> {
+    fn to_panel(&self) -> Result<Panel, Error> {
         self.to_panel_with_config(WebviewPanelConfig::default())
     }
 
-    fn to_panel_with_config(
-        &self,
-        config: WebviewPanelConfig,
-    ) -> tauri::Result<ShareId<RawNSPanel>> {
-        let panel = RawNSPanel::from_window(self.to_owned(), config);
-        let shared_panel = panel.share();
-        let manager = self.state::<self::WebviewPanelManager>();
-
-        manager
-            .0
-            .lock()
-            .unwrap()
-            .panels
-            .insert(self.label().into(), shared_panel.clone());
+    fn to_panel_with_config(&self, config: WebviewPanelConfig) -> Result<Panel, Error> {
+        let forward_events = config.forward_events;
+        let forward_mouse_events = config.forward_mouse_events;
+
+        let store = lock_store(self)?;
+
+        if store.panels.contains_key(self.label()) {
+            return match config.conversion_policy {
+                ConversionPolicy::ReuseExisting => {
+                    let panel = store.panel(self.label())?;
+
+                    // Applying the config may notify listeners, which could look up the panel
+                    drop(store);
+                    panel.apply_config(&config);
+
+                    Ok(panel)
+                }
+                ConversionPolicy::Fail => Err(Error::AlreadyConverted {
+                    label: self.label().into(),
+                }),
+            };
+        }
+
+        drop(store);
+
+        #[cfg(target_os = "macos")]
+        let shared_panel = RawNSPanel::from_window(self.to_owned(), config)?.share();
+
+        #[cfg(not(target_os = "macos"))]
+        let shared_panel = Arc::new(FallbackPanel::from_window(self.to_owned(), config));
+
+        if let Some(target) = forward_events {
+            let window = self.clone();
+
+            shared_panel.on_event(move |event| {
+                let _ = event::forward(&window, target, event);
+            });
+        }
+
+        if let Some(target) = forward_mouse_events {
+            let window = self.clone();
+
+            shared_panel.on_mouse(move |event| {
+                let _ = mouse::forward(&window, target, event);
+            });
+        }
+
+        let mut store = lock_store(self)?;
+
+        store.closed.remove(self.label());
+
+        #[cfg(target_os = "macos")]
+        {
+            store.panels.insert(
+                self.label().into(),
+                MainThreadBound::new(shared_panel.clone()),
+            );
+            store.backends.insert(
+                self.label().into(),
+                Arc::new(PanelHandle::new(
+                    self.app_handle().clone(),
+                    self.label().into(),
+                )),
+            );
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            store
+                .panels
+                .insert(self.label().into(), shared_panel.clone());
+            store
+                .backends
+                .insert(self.label().into(), Arc::new(shared_panel.clone()));
+        }
 
         Ok(shared_panel)
     }
 }
 
 /// Initializes the plugin.
-pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    Builder::new("nspanel")
-        .setup(|app, _api| {
-            app.manage(self::WebviewPanelManager::default());
+///
+/// Webview windows declared in `plugins.nspanel.panels` (see [`Config`]) are converted to panels
+/// when they are created.
+pub fn init<R: Runtime>() -> TauriPlugin<R, Option<Config>> {
+    Builder::<R, Option<Config>>::new("nspanel")
+        .invoke_handler(tauri::generate_handler![
+            commands::show,
+            commands::hide,
+            commands::toggle,
+            commands::close,
+            commands::is_visible,
+            commands::set_level,
+            commands::set_alpha_value,
+        ])
+        .setup(|app, api| {
+            #[cfg(not(target_os = "macos"))]
+            main_thread::record_main_thread();
+
+            let config = api.config().clone().unwrap_or_default();
+            let manager = self::WebviewPanelManager::default();
+
+            manager.0.lock().map_err(|_| Error::StorePoisoned)?.declared = config.panels;
+            app.manage(manager);
 
             Ok(())
         })
+        .on_webview_ready(|webview| {
+            let Ok(store) = lock_store(&webview) else {
+                return;
+            };
+
+            let Some(config) = store.declared.get(webview.label()).cloned() else {
+                return;
+            };
+
+            drop(store);
+
+            let app = webview.app_handle().clone();
+            let label = webview.label().to_owned();
+
+            let result = webview.app_handle().run_on_main_thread(move || {
+                if let Some(window) = app.get_webview_window(&label) {
+                    if let Err(error) = window.to_panel_with_config(config.into()) {
+                        log::error!("failed to convert window `{label}` to a panel: {error}");
+                    }
+                }
+            });
+
+            if let Err(error) = result {
+                log::error!(
+                    "failed to convert window `{}` to a panel: {error}",
+                    webview.label()
+                );
+            }
+        })
+        .on_event(|app, event| {
+            if let RunEvent::WindowEvent {
+                label,
+                event: WindowEvent::Destroyed,
+                ..
+            } = event
+            {
+                let Ok(mut store) = lock_store(app) else {
+                    return;
+                };
+
+                if store.panels.remove(label).is_some() {
+                    store.backends.remove(label);
+                    store.closed.insert(label.clone());
+                }
+            }
+        })
         .build()
 }
@@ -1,3 +1,4 @@
+#[cfg(target_os = "macos")]
 #[macro_export]
 macro_rules! panel_delegate {
     ($delegate_name:ident { $($fn_name:ident),* $(,)* }) => {{
@@ -8,13 +9,10 @@ macro_rules! panel_delegate {
             runtime::{self, Class, Object, Protocol, Sel},
             sel, sel_impl, Message,
         };
-        use $crate::cocoa::base::{id, nil, BOOL, NO};
+        use $crate::cocoa::base::id;
         use $crate::objc_foundation::INSObject;
-        use $crate::objc_id::{Id, ShareId};
-        use $crate::raw_nspanel::RawNSPanel;
-        use $crate::tauri::Runtime;
-        use $crate::block::ConcreteBlock;
-        use std::ffi::{c_void, c_char};
+        use $crate::PanelEvent;
+        use std::ffi::c_void;
 
         macro_rules! snake_to_camel {
             ($input:ident) => {{
@@ -88,29 +86,40 @@ macro_rules! panel_delegate {
             }
 
             extern "C" fn handle_set_listener(this: &mut Object, _: Sel, listener: *mut c_void) {
-                unsafe { this.set_ivar::<*mut c_void>("_listener", listener) };
+                unsafe {
+                    Self::drop_listener(this);
+                    this.set_ivar::<*mut c_void>("_listener", listener);
+                }
             }
 
-            $(
-                extern "C" fn $fn_name(this: &Object, _: Sel, _: id) {
-                    let delegate_name = std::ffi::CString::new(stringify!($fn_name)).unwrap();
-                    let delegate_name_ptr = delegate_name.as_ptr();
+            unsafe fn drop_listener(this: &mut Object) {
+                let listener: *mut c_void = *this.get_ivar("_listener");
+
+                if !listener.is_null() {
+                    drop(Box::from_raw(listener as *mut Box<dyn Fn(PanelEvent)>));
+                }
+            }
 
+            $(
+                extern "C" fn $fn_name(this: &Object, _: Sel, notification: id) {
                     let listener: *mut c_void = unsafe { *this.get_ivar("_listener") };
-                    let listener = listener as *const ConcreteBlock<(*const c_char, BOOL, *mut Object, *mut Sel, *mut c_void), (), ()>;
 
-                    unsafe {
-                        (*listener).call((delegate_name_ptr, NO, std::ptr::null_mut(), std::ptr::null_mut(), std::ptr::null_mut()));
+                    if listener.is_null() {
+                        return;
                     }
+
+                    let listener = unsafe { &*(listener as *const Box<dyn Fn(PanelEvent)>) };
+
+                    listener(PanelEvent::from_notification(stringify!($fn_name), notification));
                 }
             )*
 
             extern "C" fn dealloc(this: &mut Object, _cmd: Sel) {
                 unsafe {
+                    Self::drop_listener(this);
+
                     let superclass = class!(NSObject);
-                    let dealloc: extern "C" fn(&mut Object, Sel) =
-                        msg_send![super(this, superclass), dealloc];
-                    dealloc(this, _cmd);
+                    let _: () = msg_send![super(this, superclass), dealloc];
                 }
             }
         }
@@ -124,16 +133,30 @@ macro_rules! panel_delegate {
         }
 
         impl $delegate_name {
-            pub fn set_listener(&self, callback: Box<dyn Fn(String)>) {
-                let block = ConcreteBlock::new(move |_delegate_name: *const c_char| {
-                    let delegate_name = unsafe { std::ffi::CStr::from_ptr(_delegate_name).to_string_lossy().into_owned() };
-                    callback(delegate_name);
-                });
-                let block = block.copy();
-                let _: () = unsafe { msg_send![self, setListener: block] };
+            pub fn set_listener(&self, callback: Box<dyn Fn(PanelEvent)>) {
+                let listener = Box::into_raw(Box::new(callback)) as *mut c_void;
+                let _: () = unsafe { msg_send![self, setListener: listener] };
             }
         }
 
         $delegate_name::new()
     }};
 }
+
+/// Stands in for the macOS `panel_delegate!` so shared app code compiles unchanged. Windows have
+/// no delegate, so the listener is never called, observe the panel with `on_event` instead.
+#[cfg(not(target_os = "macos"))]
+#[macro_export]
+macro_rules! panel_delegate {
+    ($delegate_name:ident { $($fn_name:ident),* $(,)* }) => {{
+        #[allow(dead_code)]
+        struct $delegate_name;
+
+        impl $delegate_name {
+            #[allow(dead_code)]
+            pub fn set_listener(&self, _callback: Box<dyn Fn($crate::PanelEvent)>) {}
+        }
+
+        $delegate_name
+    }};
+}
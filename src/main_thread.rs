@@ -0,0 +1,94 @@
+use std::marker::PhantomData;
+#[cfg(target_os = "macos")]
+use std::mem::ManuallyDrop;
+#[cfg(not(target_os = "macos"))]
+use std::{sync::OnceLock, thread::ThreadId};
+
+#[cfg(target_os = "macos")]
+use cocoa::base::{BOOL, YES};
+#[cfg(target_os = "macos")]
+use objc::{class, msg_send, sel, sel_impl};
+
+/// The thread the plugin was set up on, which runs the event loop.
+#[cfg(not(target_os = "macos"))]
+static MAIN_THREAD: OnceLock<ThreadId> = OnceLock::new();
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn record_main_thread() {
+    let _ = MAIN_THREAD.set(std::thread::current().id());
+}
+
+fn is_main_thread() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        let is_main_thread: BOOL = unsafe { msg_send![class!(NSThread), isMainThread] };
+
+        is_main_thread == YES
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        MAIN_THREAD.get() == Some(&std::thread::current().id())
+    }
+}
+
+/// Proof that the current thread is the main thread, required to access a [`Panel`](crate::Panel)
+/// directly.
+///
+/// It is neither `Send` nor `Sync`, so it can't leave the main thread once obtained.
+#[derive(Debug, Clone, Copy)]
+pub struct MainThreadMarker {
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl MainThreadMarker {
+    /// Returns `None` when called off the main thread.
+    pub fn new() -> Option<Self> {
+        is_main_thread().then_some(Self {
+            _not_send: PhantomData,
+        })
+    }
+
+    /// # Safety
+    ///
+    /// Must be called on the main thread.
+    pub unsafe fn new_unchecked() -> Self {
+        Self {
+            _not_send: PhantomData,
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+/// A value that may only be used on the main thread, such as a [`Panel`](crate::Panel) kept in
+/// the `Send + Sync` plugin state.
+///
+/// The value is only reachable with a [`MainThreadMarker`]. Dropped off the main thread, it is
+/// leaked rather than released on the wrong thread.
+pub(crate) struct MainThreadBound<T>(ManuallyDrop<T>);
+
+// The value is only ever accessed or dropped on the main thread
+#[cfg(target_os = "macos")]
+unsafe impl<T> Send for MainThreadBound<T> {}
+#[cfg(target_os = "macos")]
+unsafe impl<T> Sync for MainThreadBound<T> {}
+
+#[cfg(target_os = "macos")]
+impl<T> MainThreadBound<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self(ManuallyDrop::new(value))
+    }
+
+    pub(crate) fn get(&self, _marker: MainThreadMarker) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl<T> Drop for MainThreadBound<T> {
+    fn drop(&mut self) {
+        if is_main_thread() {
+            unsafe { ManuallyDrop::drop(&mut self.0) };
+        }
+    }
+}
@@ -0,0 +1,314 @@
+//! An in-memory [`PanelBackend`] for tests, enabled with the `test` feature.
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//!
+//! use tauri::test::{mock_builder, mock_context, noop_assets};
+//! use tauri_nspanel::{
+//!     mock::{MockCall, MockPanel},
+//!     ManagerExt, PanelBackend,
+//! };
+//!
+//! let app = mock_builder()
+//!     .plugin(tauri_nspanel::init())
+//!     .build(mock_context(noop_assets()))
+//!     .unwrap();
+//!
+//! let panel = Arc::new(MockPanel::new());
+//! app.register_panel_backend("main", panel.clone()).unwrap();
+//!
+//! app.get_panel_backend("main").unwrap().show();
+//!
+//! assert!(panel.is_visible());
+//! assert_eq!(panel.calls(), vec![MockCall::Show]);
+//! ```
+
+use std::sync::Mutex;
+
+use crate::{PanelBackend, PanelLevel, PanelStyleMask};
+
+/// A call made on a [`MockPanel`], in the order it was made.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockCall {
+    Show,
+    Hide,
+    Close,
+    MakeKey,
+    ResignKey,
+    SetLevel(PanelLevel),
+    SetStyleMask(PanelStyleMask),
+    SetAlphaValue(f64),
+}
+
+#[derive(Debug)]
+struct MockState {
+    visible: bool,
+    key: bool,
+    closed: bool,
+    level: PanelLevel,
+    style_mask: PanelStyleMask,
+    alpha_value: f64,
+    calls: Vec<MockCall>,
+}
+
+/// A [`PanelBackend`] that tracks visibility, key status, level, style mask and alpha value,
+/// and records every call made on it.
+///
+/// Mirrors AppKit: hiding or closing the panel also resigns key, and making a hidden panel key
+/// doesn't show it.
+#[derive(Debug)]
+pub struct MockPanel {
+    state: Mutex<MockState>,
+}
+
+impl Default for MockPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockPanel {
+    /// Creates a hidden, non-key panel at [`PanelLevel::Floating`], the level `to_panel` applies.
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(MockState {
+                visible: false,
+                key: false,
+                closed: false,
+                level: PanelLevel::Floating,
+                style_mask: PanelStyleMask::empty(),
+                alpha_value: 1.0,
+                calls: Vec::new(),
+            }),
+        }
+    }
+
+    /// Returns every call made on the panel so far.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    /// Forgets the calls recorded so far.
+    pub fn clear_calls(&self) {
+        self.state.lock().unwrap().calls.clear();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.state.lock().unwrap().closed
+    }
+
+    fn record(&self, call: MockCall, update: impl FnOnce(&mut MockState)) {
+        let mut state = self.state.lock().unwrap();
+
+        update(&mut state);
+        state.calls.push(call);
+    }
+}
+
+impl PanelBackend for MockPanel {
+    fn show(&self) {
+        self.record(MockCall::Show, |state| {
+            state.visible = true;
+            state.key = true;
+        });
+    }
+
+    fn hide(&self) {
+        self.record(MockCall::Hide, |state| {
+            state.visible = false;
+            state.key = false;
+        });
+    }
+
+    fn close(&self) {
+        self.record(MockCall::Close, |state| {
+            state.visible = false;
+            state.key = false;
+            state.closed = true;
+        });
+    }
+
+    fn is_visible(&self) -> bool {
+        self.state.lock().unwrap().visible
+    }
+
+    fn make_key(&self) {
+        self.record(MockCall::MakeKey, |state| state.key = state.visible);
+    }
+
+    fn resign_key(&self) {
+        self.record(MockCall::ResignKey, |state| state.key = false);
+    }
+
+    fn is_key(&self) -> bool {
+        self.state.lock().unwrap().key
+    }
+
+    fn set_level(&self, level: PanelLevel) {
+        self.record(MockCall::SetLevel(level.clone()), |state| {
+            state.level = level
+        });
+    }
+
+    fn level(&self) -> PanelLevel {
+        self.state.lock().unwrap().level.clone()
+    }
+
+    fn set_style_mask(&self, style_mask: PanelStyleMask) {
+        self.record(MockCall::SetStyleMask(style_mask), |state| {
+            state.style_mask = style_mask
+        });
+    }
+
+    fn style_mask(&self) -> PanelStyleMask {
+        self.state.lock().unwrap().style_mask
+    }
+
+    fn set_alpha_value(&self, value: f64) {
+        self.record(MockCall::SetAlphaValue(value), |state| {
+            state.alpha_value = value
+        });
+    }
+
+    fn alpha_value(&self) -> f64 {
+        self.state.lock().unwrap().alpha_value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::{json, Value};
+    use tauri::{
+        ipc::{CallbackFn, InvokeBody},
+        test::{
+            get_ipc_response, mock_builder, mock_context, noop_assets, MockRuntime, INVOKE_KEY,
+        },
+        utils::acl::resolved::{Resolved, ResolvedCommand},
+        webview::InvokeRequest,
+        App, WebviewWindow, WebviewWindowBuilder,
+    };
+
+    use super::*;
+    use crate::{Error, ManagerExt};
+
+    /// Builds an app with the plugin whose `main` window may invoke `commands`, `mock_context`
+    /// grants no permissions.
+    fn mock_app(commands: &[&str]) -> App<MockRuntime> {
+        let mut context = mock_context(noop_assets());
+
+        let allowed_commands = commands
+            .iter()
+            .map(|command| {
+                let resolved = ResolvedCommand {
+                    windows: vec![glob::Pattern::new("main").unwrap()],
+                    ..Default::default()
+                };
+
+                (format!("plugin:nspanel|{command}"), vec![resolved])
+            })
+            .collect();
+
+        *context.runtime_authority_mut() = tauri::runtime_authority!(
+            Default::default(),
+            Resolved {
+                allowed_commands,
+                ..Default::default()
+            }
+        );
+
+        mock_builder().plugin(crate::init()).build(context).unwrap()
+    }
+
+    fn invoke(webview: &WebviewWindow<MockRuntime>, command: &str) -> Result<Value, Value> {
+        get_ipc_response(
+            webview,
+            InvokeRequest {
+                cmd: format!("plugin:nspanel|{command}"),
+                callback: CallbackFn(0),
+                error: CallbackFn(1),
+                url: "tauri://localhost".parse().unwrap(),
+                body: InvokeBody::Json(json!({ "label": "main" })),
+                headers: Default::default(),
+                invoke_key: INVOKE_KEY.to_string(),
+            },
+        )
+        .map(|response| response.deserialize().unwrap())
+    }
+
+    #[test]
+    fn commands_use_registered_backend() {
+        let app = mock_app(&["show", "hide", "toggle"]);
+        let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+            .build()
+            .unwrap();
+
+        let panel = Arc::new(MockPanel::new());
+        app.register_panel_backend("main", panel.clone()).unwrap();
+
+        assert_eq!(invoke(&webview, "show"), Ok(Value::Null));
+        assert!(panel.is_visible());
+        assert!(panel.is_key());
+
+        assert_eq!(invoke(&webview, "hide"), Ok(Value::Null));
+        assert!(!panel.is_visible());
+        assert!(!panel.is_key());
+
+        assert_eq!(invoke(&webview, "toggle"), Ok(json!(true)));
+        assert!(panel.is_visible());
+
+        assert_eq!(invoke(&webview, "toggle"), Ok(json!(false)));
+        assert!(!panel.is_visible());
+
+        assert_eq!(
+            panel.calls(),
+            vec![
+                MockCall::Show,
+                MockCall::Hide,
+                MockCall::Show,
+                MockCall::Hide
+            ]
+        );
+    }
+
+    #[test]
+    fn conditional_show_and_hide_report_visibility() {
+        let panel = MockPanel::new();
+
+        assert!(panel.show_if_hidden());
+        assert!(panel.show_if_hidden());
+        assert!(!panel.hide_if_visible());
+        assert!(!panel.hide_if_visible());
+
+        // Only acts when the visibility changes
+        assert_eq!(panel.calls(), vec![MockCall::Show, MockCall::Hide]);
+    }
+
+    #[test]
+    fn commands_fail_for_unknown_panels() {
+        let app = mock_app(&["show"]);
+        let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            invoke(&webview, "show"),
+            Err(json!("panel `main` not found"))
+        );
+    }
+
+    #[test]
+    fn registered_backends_are_not_panels() {
+        let app = mock_app(&[]);
+
+        app.register_panel_backend("main", Arc::new(MockPanel::new()))
+            .unwrap();
+
+        assert!(app.get_panel_backend("main").is_ok());
+        assert!(matches!(
+            app.get_panel_handle("main"),
+            Err(Error::PanelNotFound { .. })
+        ));
+    }
+}
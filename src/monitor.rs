@@ -0,0 +1,315 @@
+//! Observe input events with `NSEvent` monitors, e.g. to detect clicks outside a non-activating
+//! panel, which never resigns key.
+//!
+//! ```ignore
+//! use tauri_nspanel::monitor::{self, EventMask};
+//!
+//! let guard = monitor::add_global(EventMask::LeftMouseDown | EventMask::RightMouseDown, |event| {
+//!     println!("clicked at {:?}", event.location);
+//! });
+//!
+//! // Dropping the guard removes the monitor
+//! drop(guard);
+//! ```
+
+use bitflags::bitflags;
+use block::ConcreteBlock;
+use cocoa::{
+    base::{id, nil},
+    foundation::NSPoint,
+};
+use objc::{class, msg_send, sel, sel_impl};
+
+use crate::{raw_nspanel::primary_screen_height, PanelPoint};
+
+bitflags! {
+    /// The events a monitor receives, mirroring AppKit's `NSEventMask`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct EventMask: u64 {
+        const LeftMouseDown = 1 << 1;
+        const LeftMouseUp = 1 << 2;
+        const RightMouseDown = 1 << 3;
+        const RightMouseUp = 1 << 4;
+        const MouseMoved = 1 << 5;
+        const LeftMouseDragged = 1 << 6;
+        const RightMouseDragged = 1 << 7;
+        const KeyDown = 1 << 10;
+        const KeyUp = 1 << 11;
+        const FlagsChanged = 1 << 12;
+        const ScrollWheel = 1 << 22;
+        const OtherMouseDown = 1 << 25;
+        const OtherMouseUp = 1 << 26;
+        const OtherMouseDragged = 1 << 27;
+    }
+}
+
+bitflags! {
+    /// The modifier keys held during an event, mirroring AppKit's `NSEventModifierFlags`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ModifierFlags: u64 {
+        const CapsLock = 1 << 16;
+        const Shift = 1 << 17;
+        const Control = 1 << 18;
+        const Option = 1 << 19;
+        const Command = 1 << 20;
+        const NumericPad = 1 << 21;
+        const Help = 1 << 22;
+        const Function = 1 << 23;
+    }
+}
+
+/// The type of an [`InputEvent`], mirroring AppKit's `NSEventType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputEventKind {
+    LeftMouseDown,
+    LeftMouseUp,
+    RightMouseDown,
+    RightMouseUp,
+    MouseMoved,
+    LeftMouseDragged,
+    RightMouseDragged,
+    KeyDown,
+    KeyUp,
+    FlagsChanged,
+    ScrollWheel,
+    OtherMouseDown,
+    OtherMouseUp,
+    OtherMouseDragged,
+    /// A type without a dedicated variant, with its raw value.
+    Other(u64),
+}
+
+impl From<u64> for InputEventKind {
+    fn from(value: u64) -> Self {
+        match value {
+            1 => InputEventKind::LeftMouseDown,
+            2 => InputEventKind::LeftMouseUp,
+            3 => InputEventKind::RightMouseDown,
+            4 => InputEventKind::RightMouseUp,
+            5 => InputEventKind::MouseMoved,
+            6 => InputEventKind::LeftMouseDragged,
+            7 => InputEventKind::RightMouseDragged,
+            10 => InputEventKind::KeyDown,
+            11 => InputEventKind::KeyUp,
+            12 => InputEventKind::FlagsChanged,
+            22 => InputEventKind::ScrollWheel,
+            25 => InputEventKind::OtherMouseDown,
+            26 => InputEventKind::OtherMouseUp,
+            27 => InputEventKind::OtherMouseDragged,
+            value => InputEventKind::Other(value),
+        }
+    }
+}
+
+impl InputEventKind {
+    fn is_key(self) -> bool {
+        matches!(
+            self,
+            InputEventKind::KeyDown | InputEventKind::KeyUp | InputEventKind::FlagsChanged
+        )
+    }
+}
+
+/// An event received by a monitor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputEvent {
+    pub kind: InputEventKind,
+    /// The mouse position on the screen, in the coordinates of
+    /// [`RawNSPanel::frame`](crate::raw_nspanel::RawNSPanel::frame).
+    pub location: PanelPoint,
+    pub modifiers: ModifierFlags,
+    /// The virtual key code of key events, e.g. `53` for Escape.
+    pub key_code: Option<u16>,
+}
+
+impl InputEvent {
+    /// # Safety
+    ///
+    /// `event` must be a valid `NSEvent`.
+    unsafe fn from_ns_event(event: id) -> Self {
+        let kind: u64 = msg_send![event, type];
+        let kind = InputEventKind::from(kind);
+        let modifiers: u64 = msg_send![event, modifierFlags];
+        // In screen coordinates for events without a window, e.g. those of other apps
+        let mut location: NSPoint = msg_send![event, locationInWindow];
+        let window: id = msg_send![event, window];
+
+        if window != nil {
+            location = msg_send![window, convertPointToScreen: location];
+        }
+
+        Self {
+            kind,
+            location: PanelPoint::from(location).to_top_left(primary_screen_height()),
+            modifiers: ModifierFlags::from_bits_truncate(modifiers),
+            key_code: kind.is_key().then(|| msg_send![event, keyCode]),
+        }
+    }
+}
+
+/// What a local monitor does with the event it received.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Propagation {
+    /// Dispatch the event as usual.
+    #[default]
+    Continue,
+    /// Drop the event, the app never receives it.
+    Stop,
+}
+
+/// Removes its monitor when dropped.
+#[must_use = "the monitor is removed when the guard is dropped"]
+pub struct MonitorGuard {
+    monitor: id,
+}
+
+impl MonitorGuard {
+    fn new(monitor: id) -> Self {
+        if monitor == nil {
+            log::warn!("failed to add an event monitor");
+
+            return Self { monitor };
+        }
+
+        let monitor: id = unsafe { msg_send![monitor, retain] };
+
+        Self { monitor }
+    }
+
+    /// Whether AppKit installed the monitor. When it didn't, the callback is never called.
+    pub fn is_active(&self) -> bool {
+        self.monitor != nil
+    }
+}
+
+impl Drop for MonitorGuard {
+    fn drop(&mut self) {
+        if self.monitor == nil {
+            return;
+        }
+
+        unsafe {
+            let _: () = msg_send![class!(NSEvent), removeMonitor: self.monitor];
+            let _: () = msg_send![self.monitor, release];
+        }
+    }
+}
+
+/// Calls `callback` with the events of `mask` sent to other apps, on the main thread.
+///
+/// Key events are only received once the user granted the app accessibility access. If AppKit
+/// doesn't install the monitor, a warning is logged and [`MonitorGuard::is_active`] is `false`.
+pub fn add_global<F: Fn(&InputEvent) + 'static>(mask: EventMask, callback: F) -> MonitorGuard {
+    let handler = ConcreteBlock::new(move |event: id| {
+        callback(&unsafe { InputEvent::from_ns_event(event) });
+    })
+    .copy();
+
+    let monitor: id = unsafe {
+        msg_send![
+            class!(NSEvent),
+            addGlobalMonitorForEventsMatchingMask: mask.bits()
+            handler: &*handler
+        ]
+    };
+
+    MonitorGuard::new(monitor)
+}
+
+/// Calls `callback` with the events of `mask` sent to this app before they are dispatched. The
+/// event is dropped when `callback` returns [`Propagation::Stop`].
+pub fn add_local<F: Fn(&InputEvent) -> Propagation + 'static>(
+    mask: EventMask,
+    callback: F,
+) -> MonitorGuard {
+    let handler = ConcreteBlock::new(move |event: id| -> id {
+        match callback(&unsafe { InputEvent::from_ns_event(event) }) {
+            Propagation::Continue => event,
+            Propagation::Stop => nil,
+        }
+    })
+    .copy();
+
+    let monitor: id = unsafe {
+        msg_send![
+            class!(NSEvent),
+            addLocalMonitorForEventsMatchingMask: mask.bits()
+            handler: &*handler
+        ]
+    };
+
+    MonitorGuard::new(monitor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KINDS: [(u64, InputEventKind, EventMask); 14] = [
+        (1, InputEventKind::LeftMouseDown, EventMask::LeftMouseDown),
+        (2, InputEventKind::LeftMouseUp, EventMask::LeftMouseUp),
+        (3, InputEventKind::RightMouseDown, EventMask::RightMouseDown),
+        (4, InputEventKind::RightMouseUp, EventMask::RightMouseUp),
+        (5, InputEventKind::MouseMoved, EventMask::MouseMoved),
+        (
+            6,
+            InputEventKind::LeftMouseDragged,
+            EventMask::LeftMouseDragged,
+        ),
+        (
+            7,
+            InputEventKind::RightMouseDragged,
+            EventMask::RightMouseDragged,
+        ),
+        (10, InputEventKind::KeyDown, EventMask::KeyDown),
+        (11, InputEventKind::KeyUp, EventMask::KeyUp),
+        (12, InputEventKind::FlagsChanged, EventMask::FlagsChanged),
+        (22, InputEventKind::ScrollWheel, EventMask::ScrollWheel),
+        (
+            25,
+            InputEventKind::OtherMouseDown,
+            EventMask::OtherMouseDown,
+        ),
+        (26, InputEventKind::OtherMouseUp, EventMask::OtherMouseUp),
+        (
+            27,
+            InputEventKind::OtherMouseDragged,
+            EventMask::OtherMouseDragged,
+        ),
+    ];
+
+    #[test]
+    fn kinds_match_their_raw_value() {
+        for (value, kind, _) in KINDS {
+            assert_eq!(InputEventKind::from(value), kind);
+        }
+    }
+
+    #[test]
+    fn masks_match_the_raw_value_of_their_kind() {
+        for (value, _, mask) in KINDS {
+            assert_eq!(mask.bits(), 1 << value);
+        }
+
+        let all = KINDS
+            .iter()
+            .fold(EventMask::empty(), |all, (_, _, mask)| all | *mask);
+        assert_eq!(all, EventMask::all());
+    }
+
+    #[test]
+    fn unknown_kinds_keep_their_raw_value() {
+        assert_eq!(InputEventKind::from(0), InputEventKind::Other(0));
+        assert_eq!(InputEventKind::from(9), InputEventKind::Other(9));
+    }
+
+    #[test]
+    fn only_keyboard_kinds_are_keys() {
+        assert!(InputEventKind::KeyDown.is_key());
+        assert!(InputEventKind::KeyUp.is_key());
+        assert!(InputEventKind::FlagsChanged.is_key());
+        assert!(!InputEventKind::LeftMouseDown.is_key());
+        assert!(!InputEventKind::ScrollWheel.is_key());
+        assert!(!InputEventKind::Other(9).is_key());
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(target_os = "macos")]
+use cocoa::{
+    base::id,
+    foundation::{NSPoint, NSRect},
+};
+#[cfg(target_os = "macos")]
+use objc::{msg_send, sel, sel_impl};
+use serde::Serialize;
+use tauri::{Emitter, EventTarget, Runtime, WebviewWindow};
+
+#[cfg(target_os = "macos")]
+use crate::raw_nspanel::primary_screen_height;
+use crate::{PanelEventTarget, PanelPoint};
+
+/// A mouse event from the tracking area of a panel, see [`RawNSPanel::on_mouse`].
+///
+/// `position` is relative to the panel's window and `screen_position` to the primary screen, both
+/// with a top-left origin, like [`RawNSPanel::frame`].
+///
+/// [`RawNSPanel::on_mouse`]: crate::raw_nspanel::RawNSPanel::on_mouse
+/// [`RawNSPanel::frame`]: crate::raw_nspanel::RawNSPanel::frame
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum MouseTrackingEvent {
+    /// `mouseEntered:`
+    Entered {
+        position: PanelPoint,
+        screen_position: PanelPoint,
+    },
+    /// `mouseExited:`
+    Exited {
+        position: PanelPoint,
+        screen_position: PanelPoint,
+    },
+    /// `mouseMoved:`
+    Moved {
+        position: PanelPoint,
+        screen_position: PanelPoint,
+    },
+    /// `cursorUpdate:`
+    CursorUpdate {
+        position: PanelPoint,
+        screen_position: PanelPoint,
+    },
+}
+
+impl MouseTrackingEvent {
+    /// Builds an event from a snake case tracking callback name, e.g. `mouse_moved`, and the
+    /// `NSEvent` passed to `window`.
+    #[cfg(target_os = "macos")]
+    pub(crate) fn from_ns_event(name: &str, event: id, window: id) -> Option<Self> {
+        let (position, screen_position) = unsafe {
+            // Unlike `[NSEvent mouseLocation]`, where the mouse was when the event was posted
+            let location: NSPoint = msg_send![event, locationInWindow];
+            let frame: NSRect = msg_send![window, frame];
+
+            let position = PanelPoint {
+                x: location.x,
+                y: frame.size.height - location.y,
+            };
+            let screen_position = PanelPoint {
+                x: frame.origin.x + location.x,
+                y: frame.origin.y + location.y,
+            };
+
+            (
+                position,
+                screen_position.to_top_left(primary_screen_height()),
+            )
+        };
+
+        Some(match name {
+            "mouse_entered" => MouseTrackingEvent::Entered {
+                position,
+                screen_position,
+            },
+            "mouse_exited" => MouseTrackingEvent::Exited {
+                position,
+                screen_position,
+            },
+            "mouse_moved" => MouseTrackingEvent::Moved {
+                position,
+                screen_position,
+            },
+            "cursor_update" => MouseTrackingEvent::CursorUpdate {
+                position,
+                screen_position,
+            },
+            _ => return None,
+        })
+    }
+
+    pub fn position(&self) -> PanelPoint {
+        match self {
+            MouseTrackingEvent::Entered { position, .. }
+            | MouseTrackingEvent::Exited { position, .. }
+            | MouseTrackingEvent::Moved { position, .. }
+            | MouseTrackingEvent::CursorUpdate { position, .. } => *position,
+        }
+    }
+
+    pub fn screen_position(&self) -> PanelPoint {
+        match self {
+            MouseTrackingEvent::Entered {
+                screen_position, ..
+            }
+            | MouseTrackingEvent::Exited {
+                screen_position, ..
+            }
+            | MouseTrackingEvent::Moved {
+                screen_position, ..
+            }
+            | MouseTrackingEvent::CursorUpdate {
+                screen_position, ..
+            } => *screen_position,
+        }
+    }
+
+    /// Returns the name of the Tauri event this event is forwarded as, e.g. `nspanel://mouse-moved`.
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            MouseTrackingEvent::Entered { .. } => "nspanel://mouse-entered",
+            MouseTrackingEvent::Exited { .. } => "nspanel://mouse-exited",
+            MouseTrackingEvent::Moved { .. } => "nspanel://mouse-moved",
+            MouseTrackingEvent::CursorUpdate { .. } => "nspanel://cursor-update",
+        }
+    }
+}
+
+/// Payload of the `nspanel://mouse-*` and `nspanel://cursor-update` events emitted to the
+/// frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct MouseTrackingEventPayload {
+    pub label: String,
+    pub event: MouseTrackingEvent,
+}
+
+/// Emits `event` as a Tauri event on behalf of the panel backed by `window`.
+pub(crate) fn forward<R: Runtime>(
+    window: &WebviewWindow<R>,
+    target: PanelEventTarget,
+    event: MouseTrackingEvent,
+) -> tauri::Result<()> {
+    let name = event.event_name();
+    let payload = MouseTrackingEventPayload {
+        label: window.label().to_owned(),
+        event,
+    };
+
+    match target {
+        PanelEventTarget::Webview => {
+            window.emit_to(EventTarget::webview_window(window.label()), name, payload)
+        }
+        PanelEventTarget::App => window.emit(name, payload),
+    }
+}
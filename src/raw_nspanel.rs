@@ -1,12 +1,10 @@
-use std::{thread::sleep, time::Duration};
+use std::{cell::RefCell, collections::HashMap, future::Future, rc::Rc, time::Instant};
 
-use bitflags::bitflags;
+use block::ConcreteBlock;
 use cocoa::{
-    appkit::{
-        NSView as NSViewOld, NSViewHeightSizable, NSViewWidthSizable, NSWindowCollectionBehavior,
-    },
+    appkit::{NSView as NSViewOld, NSViewHeightSizable, NSViewWidthSizable},
     base::{id, nil, BOOL, NO, YES},
-    foundation::NSRect,
+    foundation::{NSInteger, NSPoint, NSRect, NSString, NSUInteger},
 };
 // use objc2_app_kit::{NSPanel, NSView};
 
@@ -19,18 +17,25 @@ use objc::{
 };
 use objc_foundation::INSObject;
 use objc_id::{Id, ShareId};
+#[cfg(feature = "tray-icon")]
+use tauri::tray::TrayIconEvent;
 use tauri::{Runtime, WebviewWindow};
 
-use super::WebviewPanelConfig;
-
-bitflags! {
-    struct NSTrackingAreaOptionsOld: u32 {
-        const NSTrackingActiveAlways = 0x80;
-        const NSTrackingMouseEnteredAndExited = 0x01;
-        const NSTrackingMouseMoved = 0x02;
-        const NSTrackingCursorUpdate = 0x04;
-    }
-}
+#[cfg(feature = "tray-icon")]
+use crate::anchor::tray_icon_rect;
+
+use crate::{
+    anchor::anchor_position,
+    animation::{self, Animation, AnimationDirection, Keyframe},
+    dismiss::DismissPolicy,
+    event::{DID_HIDE_NOTIFICATION, DID_SHOW_NOTIFICATION, WINDOW_NOTIFICATIONS},
+    hover::{HoverAction, HoverScheduler, Schedule},
+    monitor::{self, EventMask, MonitorGuard, Propagation},
+    screen::{self, move_between_screens, PanelScreen, ScreenSource, ScreenTarget},
+    AnchorOptions, ConversionPolicy, Error, FocusOptions, MainThreadMarker, MouseTrackingEvent,
+    PanelCapabilities, PanelCollectionBehavior, PanelEvent, PanelLevel, PanelPoint, PanelRect,
+    PanelStyleMask, TrackingArea, TrackingAreaId, WebviewPanelConfig,
+};
 
 extern "C" {
     pub fn object_setClass(obj: id, cls: id) -> id;
@@ -44,10 +49,104 @@ extern "C" {
 
 const CLS_NAME: &str = "RawNSPanel";
 
-pub struct RawNSPanel;
+const ESCAPE_KEY_CODE: u16 = 53;
+
+#[derive(Default)]
+struct PanelState {
+    focus: FocusOptions,
+    hover: HoverScheduler,
+    mouse_listeners: Vec<Rc<dyn Fn(MouseTrackingEvent)>>,
+    tracking_areas: Vec<(TrackingAreaId, TrackingArea, id)>,
+    next_tracking_area: u64,
+    /// The retained content view observed for frame changes while there are tracking areas.
+    observed_content_view: Option<id>,
+    screen_target: Option<ScreenTarget>,
+    animation: Option<RunningAnimation>,
+    dismiss: DismissPolicy,
+    pinned: bool,
+    dismiss_observers: Option<DismissObservers>,
+    /// Retained observers added by [`RawNSPanel::on_event`], removed when the panel closes.
+    event_observers: Vec<id>,
+    /// Set while the focus policy resigns key on mouse exit, which isn't the user clicking away.
+    resigning_on_hover: bool,
+}
 
-unsafe impl Sync for RawNSPanel {}
-unsafe impl Send for RawNSPanel {}
+/// Implements the [`DismissPolicy`] of a panel, installed the first time one is set.
+struct DismissObservers {
+    resign_key: id,
+    resign_active: id,
+    escape: MonitorGuard,
+}
+
+impl DismissObservers {
+    fn remove(self) {
+        let Self {
+            resign_key,
+            resign_active,
+            escape,
+        } = self;
+
+        remove_observers(vec![resign_key, resign_active]);
+
+        // Dropping the guard releases the monitor's block, which may be the one running
+        run_later(move || drop(escape));
+    }
+}
+
+/// An animation in flight, see [`RawNSPanel::show_animated`].
+struct RunningAnimation {
+    timer: id,
+    /// The frame and alpha value the panel has when shown.
+    frame: PanelRect,
+    alpha: f64,
+}
+
+/// Removes observers returned by `addObserverForName:object:queue:usingBlock:`, and releases them
+/// along with their blocks once the current event has been handled, as one of the blocks may be
+/// running.
+fn remove_observers(observers: Vec<id>) {
+    unsafe {
+        let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+
+        for &observer in &observers {
+            let _: () = msg_send![center, removeObserver: observer];
+        }
+    }
+
+    run_later(move || {
+        for observer in observers {
+            let _: () = unsafe { msg_send![observer, release] };
+        }
+    });
+}
+
+/// Runs `f` on the main run loop once the current event has been handled.
+fn run_later<F: FnOnce() + 'static>(f: F) {
+    let f = RefCell::new(Some(f));
+    let block = ConcreteBlock::new(move || {
+        if let Some(f) = f.borrow_mut().take() {
+            f();
+        }
+    })
+    .copy();
+
+    unsafe {
+        let run_loop: id = msg_send![class!(NSRunLoop), mainRunLoop];
+        let _: () = msg_send![run_loop, performBlock: &*block];
+    }
+}
+
+thread_local! {
+    /// State of each panel, keyed by its address. Windows are converted by swapping their class,
+    /// which can't add instance variables to hold it.
+    static PANEL_STATE: RefCell<HashMap<usize, PanelState>> = RefCell::default();
+}
+
+fn with_panel_state<T>(key: usize, f: impl FnOnce(&mut PanelState) -> T) -> T {
+    PANEL_STATE.with(|state| f(state.borrow_mut().entry(key).or_default()))
+}
+
+pub struct RawNSPanel;
 
 impl INSObject for RawNSPanel {
     fn class() -> &'static runtime::Class {
@@ -56,46 +155,144 @@ impl INSObject for RawNSPanel {
 }
 
 impl RawNSPanel {
-    /// Returns YES to ensure that RawNSPanel can become a key window
-    extern "C" fn can_become_key_window(_: &Object, _: Sel) -> BOOL {
-        YES // [UPDATED COMMENT] if "NO", the panel becomes a key window only when you click on it (previous behavior)
+    extern "C" fn yes(_: &Object, _: Sel) -> BOOL {
+        YES
     }
 
-    // Add this new method to prevent automatic resignation
-    extern "C" fn can_resign_key_window(_: &Object, _: Sel) -> BOOL {
-        // Return NO to prevent the panel from automatically resigning key window status
-        NO // [UPDATED COMMENT] Making it "YES" doesn't change anything functionally if we have the mouse tracking area
+    extern "C" fn no(_: &Object, _: Sel) -> BOOL {
+        NO
     }
 
     extern "C" fn dealloc(this: &mut Object, _cmd: Sel) {
+        let key = this as *const _ as usize;
+
+        // Neither an animation nor a pending hover change is left, they retain the panel
+        unsafe { &*(this as *const Object as *const RawNSPanel) }.remove_registrations();
+        PANEL_STATE.with(|state| state.borrow_mut().remove(&key));
+
         unsafe {
-            let superclass = class!(NSObject);
-            let dealloc: extern "C" fn(&mut Object, Sel) =
-                msg_send![super(this, superclass), dealloc];
-            dealloc(this, _cmd);
+            let _: () = msg_send![super(this, class!(NSPanel)), dealloc];
         }
     }
 
-    extern "C" fn mouse_entered(_this: &Object, _sel: Sel, _event: id) {
+    /// Tears the panel down once it closed, see [`RawNSPanel::tear_down`].
+    extern "C" fn close_window(this: &Object, _sel: Sel) {
         unsafe {
-            let this: id = _this as *const _ as id;
+            let _: () = msg_send![super(this, class!(NSPanel)), close];
+        }
 
-            // Force the panel to become key and active
-            let _: () = msg_send![this, makeKeyWindow];
+        unsafe { &*(this as *const Object as *const RawNSPanel) }.tear_down();
+    }
 
-            // Add explicit type annotation for the content view
-            let content_view: id = msg_send![this, contentView];
-            let _: () = msg_send![this, makeFirstResponder: content_view];
-        }
+    extern "C" fn mouse_entered(this: &Object, _sel: Sel, event: id) {
+        Self::notify_mouse(this, "mouse_entered", event);
+        Self::schedule_hover(this, HoverAction::MakeKey);
     }
 
-    extern "C" fn mouse_exited(_this: &Object, _sel: Sel, _event: id) {
+    extern "C" fn mouse_exited(this: &Object, _sel: Sel, event: id) {
+        Self::notify_mouse(this, "mouse_exited", event);
+
         // resign key window - THIS FIXES KEYBOARD FOCUS NOT BEING RETURNED ON MOUSE EXIT
-        // [TESTING] on mouse exit wait 20ms before resigning key window so the UI can update any potential hover effects
+        // the delay lets the UI update any potential hover effects first
+        Self::schedule_hover(this, HoverAction::ResignKey);
+    }
+
+    extern "C" fn mouse_moved(this: &Object, _sel: Sel, event: id) {
+        Self::notify_mouse(this, "mouse_moved", event);
+
+        unsafe {
+            let _: () = msg_send![super(this, class!(NSPanel)), mouseMoved: event];
+        }
+    }
 
-        sleep(Duration::from_millis(20));
+    extern "C" fn cursor_update(this: &Object, _sel: Sel, event: id) {
+        Self::notify_mouse(this, "cursor_update", event);
 
-        let _: () = unsafe { msg_send![_this, resignKeyWindow] };
+        unsafe {
+            let _: () = msg_send![super(this, class!(NSPanel)), cursorUpdate: event];
+        }
+    }
+
+    fn notify_mouse(this: &Object, name: &str, event: id) {
+        let key = this as *const _ as usize;
+
+        // Listeners may register other listeners, so don't hold the state while calling them
+        let listeners = with_panel_state(key, |state| state.mouse_listeners.clone());
+
+        if listeners.is_empty() {
+            return;
+        }
+
+        if let Some(event) = MouseTrackingEvent::from_ns_event(name, event, this as *const _ as id)
+        {
+            for listener in listeners {
+                listener(event);
+            }
+        }
+    }
+
+    /// Runs `action` after the delay of the panel's focus options, without blocking the run loop.
+    fn schedule_hover(this: &Object, action: HoverAction) {
+        let key = this as *const _ as usize;
+
+        let schedule = with_panel_state(key, |state| {
+            if !state.focus.policy.key_on_hover() {
+                return None;
+            }
+
+            let delay = match action {
+                HoverAction::MakeKey => state.focus.enter_delay,
+                HoverAction::ResignKey => state.focus.exit_delay,
+            };
+
+            Some(state.hover.schedule(action, delay))
+        });
+
+        match schedule {
+            Some(Schedule::Now(action)) => Self::perform_hover(this, action),
+            Some(Schedule::Later { generation, delay }) => unsafe {
+                let generation: id =
+                    msg_send![class!(NSNumber), numberWithUnsignedLongLong: generation];
+                let _: () = msg_send![
+                    this,
+                    performSelector: sel!(fireHoverAction:)
+                    withObject: generation
+                    afterDelay: delay.as_secs_f64()
+                ];
+            },
+            None => {}
+        }
+    }
+
+    extern "C" fn fire_hover_action(this: &Object, _sel: Sel, generation: id) {
+        let key = this as *const _ as usize;
+        let generation: u64 = unsafe { msg_send![generation, unsignedLongLongValue] };
+
+        if let Some(action) = with_panel_state(key, |state| state.hover.fire(generation)) {
+            Self::perform_hover(this, action);
+        }
+    }
+
+    fn perform_hover(this: &Object, action: HoverAction) {
+        unsafe {
+            match action {
+                HoverAction::MakeKey => {
+                    // Force the panel to become key and active
+                    let _: () = msg_send![this, makeKeyWindow];
+
+                    // Add explicit type annotation for the content view
+                    let content_view: id = msg_send![this, contentView];
+                    let _: () = msg_send![this, makeFirstResponder: content_view];
+                }
+                HoverAction::ResignKey => {
+                    let key = this as *const _ as usize;
+
+                    with_panel_state(key, |state| state.resigning_on_hover = true);
+                    let _: () = msg_send![this, resignKeyWindow];
+                    with_panel_state(key, |state| state.resigning_on_hover = false);
+                }
+            }
+        }
     }
 
     fn define_class() -> &'static Class {
@@ -103,22 +300,22 @@ impl RawNSPanel {
             .unwrap_or_else(|| panic!("Unable to register {} class", CLS_NAME));
 
         unsafe {
+            // If NO, the panel becomes a key window only when you click on it
             cls.add_method(
                 sel!(canBecomeKeyWindow),
-                Self::can_become_key_window as extern "C" fn(&Object, Sel) -> BOOL,
+                Self::yes as extern "C" fn(&Object, Sel) -> BOOL,
             );
 
-            // Add this new method to prevent the panel from resigning key window status - NOTE THIS DOESN'T EXIST
-            // cls.add_method(
-            //     sel!(canResignKeyWindow),
-            //     Self::can_resign_key_window as extern "C" fn(&Object, Sel) -> BOOL,
-            // );
-
             cls.add_method(
                 sel!(dealloc),
                 Self::dealloc as extern "C" fn(&mut Object, Sel), // not needed anymore
             );
 
+            cls.add_method(
+                sel!(close),
+                Self::close_window as extern "C" fn(&Object, Sel),
+            );
+
             // Add mouse tracking methods
             cls.add_method(
                 sel!(mouseEntered:),
@@ -129,17 +326,396 @@ impl RawNSPanel {
                 sel!(mouseExited:),
                 Self::mouse_exited as extern "C" fn(&Object, Sel, id),
             );
+
+            cls.add_method(
+                sel!(mouseMoved:),
+                Self::mouse_moved as extern "C" fn(&Object, Sel, id),
+            );
+
+            cls.add_method(
+                sel!(cursorUpdate:),
+                Self::cursor_update as extern "C" fn(&Object, Sel, id),
+            );
+
+            cls.add_method(
+                sel!(contentViewFrameDidChange:),
+                Self::content_view_frame_did_change as extern "C" fn(&Object, Sel, id),
+            );
+
+            cls.add_method(
+                sel!(fireHoverAction:),
+                Self::fire_hover_action as extern "C" fn(&Object, Sel, id),
+            );
+        }
+
+        cls.register()
+    }
+
+    /// Returns the `RawNSPanel` subclass overriding `canBecomeKeyWindow` and
+    /// `canBecomeMainWindow` for `capabilities`, registering it on first use.
+    ///
+    /// The default capabilities use `RawNSPanel` itself.
+    pub fn class_for(capabilities: PanelCapabilities) -> &'static Class {
+        if capabilities == PanelCapabilities::default() {
+            return Self::class();
+        }
+
+        let name = format!(
+            "{}_Key{}_Main{}",
+            CLS_NAME,
+            capabilities.can_become_key_window as u8,
+            capabilities.can_become_main_window as u8
+        );
+
+        if let Some(cls) = Class::get(&name) {
+            return cls;
+        }
+
+        let mut cls = ClassDecl::new(&name, Self::class())
+            .unwrap_or_else(|| panic!("Unable to register {} class", name));
+
+        let method = |value: bool| match value {
+            true => Self::yes as extern "C" fn(&Object, Sel) -> BOOL,
+            false => Self::no as extern "C" fn(&Object, Sel) -> BOOL,
+        };
+
+        unsafe {
+            cls.add_method(
+                sel!(canBecomeKeyWindow),
+                method(capabilities.can_become_key_window),
+            );
+            cls.add_method(
+                sel!(canBecomeMainWindow),
+                method(capabilities.can_become_main_window),
+            );
         }
 
         cls.register()
     }
 
+    /// Whether `window` was already converted to a `RawNSPanel` or one of its subclasses.
+    fn is_panel(window: id) -> bool {
+        let is_panel: BOOL = unsafe { msg_send![window, isKindOfClass: Self::class()] };
+
+        is_panel == YES
+    }
+
+    /// Shows the panel, first moving it to its screen target if one is set, see
+    /// [`RawNSPanel::set_screen_target`].
     pub fn show(&self) {
+        if let Some(target) = self.screen_target() {
+            self.move_to_screen(target);
+        }
+
+        self.order_front();
+    }
+
+    /// Shows the panel on the screen `target`.
+    pub fn show_on(&self, target: ScreenTarget) {
+        self.move_to_screen(target);
+        self.order_front();
+    }
+
+    /// Moves the panel to the screen `target`, keeping its relative position on the screen. See
+    /// [`move_between_screens`].
+    pub fn move_to_screen(&self, target: ScreenTarget) {
+        let Some(to) = target.resolve(&AppKitScreens) else {
+            return;
+        };
+
+        let frame = self.frame();
+        let screens = AppKitScreens.screens();
+
+        let Some(from) = screen::screen_of(&screens, frame) else {
+            return;
+        };
+
+        if *from != to {
+            self.set_frame(move_between_screens(frame, from, &to), false);
+        }
+    }
+
+    /// Sets the screen [`RawNSPanel::show`] and [`RawNSPanel::activate`] move the panel to, or
+    /// `None` to show it where it is.
+    pub fn set_screen_target(&self, target: Option<ScreenTarget>) {
+        with_panel_state(self as *const _ as usize, |state| {
+            state.screen_target = target
+        });
+    }
+
+    pub fn screen_target(&self) -> Option<ScreenTarget> {
+        with_panel_state(self as *const _ as usize, |state| state.screen_target)
+    }
+
+    /// Shows the panel with `animation`, like [`RawNSPanel::show`], then calls `on_complete`.
+    ///
+    /// Starting another animation interrupts this one, and drops `on_complete` without calling
+    /// it. Motion is replaced with a fade when the user asked the system to reduce motion.
+    pub fn show_animated<F: FnOnce() + 'static>(&self, animation: Animation, on_complete: F) {
+        self.animate(animation, AnimationDirection::In, on_complete);
+    }
+
+    /// Hides the panel with `animation`, then calls `on_complete`. See
+    /// [`RawNSPanel::show_animated`].
+    pub fn hide_animated<F: FnOnce() + 'static>(&self, animation: Animation, on_complete: F) {
+        self.animate(animation, AnimationDirection::Out, on_complete);
+    }
+
+    /// Shows the panel with `animation` and returns a future that resolves once it finished or
+    /// another animation interrupted it. The future is `Send`, so it can be awaited anywhere.
+    pub fn show_animated_async(
+        &self,
+        animation: Animation,
+    ) -> impl Future<Output = ()> + Send + 'static {
+        let (on_complete, completed) = animation::completion();
+        self.show_animated(animation, on_complete);
+
+        completed
+    }
+
+    /// Hides the panel with `animation`, see [`RawNSPanel::show_animated_async`].
+    pub fn hide_animated_async(
+        &self,
+        animation: Animation,
+    ) -> impl Future<Output = ()> + Send + 'static {
+        let (on_complete, completed) = animation::completion();
+        self.hide_animated(animation, on_complete);
+
+        completed
+    }
+
+    fn animate<F: FnOnce() + 'static>(
+        &self,
+        animation: Animation,
+        direction: AnimationDirection,
+        on_complete: F,
+    ) {
+        let key = self as *const _ as usize;
+        let animation = if reduce_motion() {
+            animation.with_reduced_motion()
+        } else {
+            animation
+        };
+
+        // An interrupted animation left the panel somewhere along the way, start from where it
+        // was headed instead
+        let (frame, alpha) = match with_panel_state(key, |state| state.animation.take()) {
+            Some(running) => {
+                let _: () = unsafe { msg_send![running.timer, invalidate] };
+                self.set_frame(running.frame, false);
+                (running.frame, running.alpha)
+            }
+            None => (self.frame(), self.alpha_value()),
+        };
+
+        if direction == AnimationDirection::In {
+            if !self.is_visible() {
+                self.set_alpha_value(0.0);
+            }
+
+            self.show();
+        }
+
+        // `show` may have moved the panel to another screen
+        let frame = match direction {
+            AnimationDirection::In => self.frame(),
+            AnimationDirection::Out => frame,
+        };
+
+        let panel = unsafe { ShareId::from_ptr(self as *const Self as *mut Self) };
+        let start = Instant::now();
+        let on_complete = RefCell::new(Some(on_complete));
+
+        let tick = move |timer: id| {
+            let elapsed = start.elapsed();
+
+            panel.apply_keyframe(animation.keyframe(frame, elapsed, direction), alpha);
+
+            if !animation.is_finished(elapsed) {
+                return;
+            }
+
+            let _: () = unsafe { msg_send![timer, invalidate] };
+
+            with_panel_state(key, |state| {
+                if state
+                    .animation
+                    .as_ref()
+                    .is_some_and(|running| running.timer == timer)
+                {
+                    state.animation = None;
+                }
+            });
+
+            // Restores the panel, so it shows as it was the next time
+            if direction == AnimationDirection::Out {
+                panel.order_out(None);
+                panel.set_alpha_value(alpha);
+                panel.set_frame(frame, false);
+            }
+
+            if let Some(on_complete) = on_complete.borrow_mut().take() {
+                on_complete();
+            }
+        };
+
+        if animation.duration.is_zero() {
+            return tick(nil);
+        }
+
+        let block = ConcreteBlock::new(tick).copy();
+        let timer: id = unsafe {
+            msg_send![class!(NSTimer), scheduledTimerWithTimeInterval: 1.0 / 60.0
+                repeats: YES
+                block: &*block]
+        };
+
+        with_panel_state(key, |state| {
+            state.animation = Some(RunningAnimation {
+                timer,
+                frame,
+                alpha,
+            })
+        });
+    }
+
+    fn apply_keyframe(&self, keyframe: Keyframe, alpha: f64) {
+        self.set_alpha_value(keyframe.alpha * alpha);
+        self.set_frame(keyframe.frame, false);
+    }
+
+    /// Sets when the panel hides itself, see [`DismissPolicy`].
+    pub fn set_dismiss_policy(&self, policy: DismissPolicy) {
+        let key = self as *const _ as usize;
+        let install = with_panel_state(key, |state| {
+            state.dismiss = policy;
+            !policy.is_empty() && state.dismiss_observers.is_none()
+        });
+
+        if install {
+            let observers = self.observe_dismiss();
+            with_panel_state(key, |state| state.dismiss_observers = Some(observers));
+        }
+    }
+
+    pub fn dismiss_policy(&self) -> DismissPolicy {
+        with_panel_state(self as *const _ as usize, |state| state.dismiss)
+    }
+
+    /// Suspends the dismiss policy while `pinned`, e.g. while the panel shows a dialog.
+    pub fn set_pinned(&self, pinned: bool) {
+        with_panel_state(self as *const _ as usize, |state| state.pinned = pinned);
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        with_panel_state(self as *const _ as usize, |state| state.pinned)
+    }
+
+    /// Hides the panel if `trigger` is part of its dismiss policy, returning whether it did.
+    fn dismiss(key: usize, trigger: DismissPolicy) -> bool {
+        let dismiss = with_panel_state(key, |state| {
+            state.dismiss.contains(trigger) && !state.pinned && !state.resigning_on_hover
+        });
+
+        if dismiss {
+            unsafe { &*(key as *const RawNSPanel) }.order_out(None);
+        }
+
+        dismiss
+    }
+
+    /// The observers only hold the address of the panel, they are removed when it closes, is
+    /// unregistered or deallocated. Set the policy again to reinstall them.
+    fn observe_dismiss(&self) -> DismissObservers {
+        let key = self as *const _ as usize;
+
+        unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+
+            let observe = |name: &str, object: id, trigger: DismissPolicy| -> id {
+                let block = ConcreteBlock::new(move |_notification: id| {
+                    Self::dismiss(key, trigger);
+                })
+                .copy();
+
+                let name = NSString::alloc(nil).init_str(name);
+                let observer: id = msg_send![
+                    center,
+                    addObserverForName: name
+                    object: object
+                    queue: nil
+                    usingBlock: &*block
+                ];
+                let _: () = msg_send![name, release];
+
+                msg_send![observer, retain]
+            };
+
+            let resign_key = observe(
+                "NSWindowDidResignKeyNotification",
+                self as *const _ as id,
+                DismissPolicy::ResignKey,
+            );
+            let resign_active = observe(
+                "NSApplicationDidResignActiveNotification",
+                nil,
+                DismissPolicy::AppDeactivate,
+            );
+
+            // Swallows the Escape key down when it dismisses the panel
+            let escape = monitor::add_local(EventMask::KeyDown, move |event| {
+                // The monitor is released after its observers were removed, maybe along with the
+                // panel, so don't touch the panel unless they are still installed
+                let installed = PANEL_STATE.with(|state| {
+                    state
+                        .borrow()
+                        .get(&key)
+                        .is_some_and(|state| state.dismiss_observers.is_some())
+                });
+
+                if event.key_code != Some(ESCAPE_KEY_CODE) || !installed {
+                    return Propagation::Continue;
+                }
+
+                let is_key: BOOL = msg_send![key as id, isKeyWindow];
+
+                if is_key == YES && Self::dismiss(key, DismissPolicy::Escape) {
+                    Propagation::Stop
+                } else {
+                    Propagation::Continue
+                }
+            });
+
+            DismissObservers {
+                resign_key,
+                resign_active,
+                escape,
+            }
+        }
+    }
+
+    fn order_front(&self) {
         self.make_first_responder(Some(self.content_view()));
         self.order_front_regardless();
-        self.make_key_window(); // technically not needed, not sure why it's here. This would make the panel key when showing it
-                                // meaning the keyboard focus would move to this panel even though the mouse hasn't entered it yet (aka the user hasn't acknowledged the panel yet)
-                                // comment this out if creating window at runtime instead of during app launch (I'm currently creating the win as hidden and showing it only when needed)
+
+        // Moves keyboard focus to the panel even though the mouse hasn't entered it yet
+        if self.focus_options().policy.key_on_show() {
+            self.make_key_window();
+        }
+    }
+
+    /// Sets when the panel becomes key, see [`FocusPolicy`](crate::FocusPolicy).
+    pub fn set_focus_options(&self, options: impl Into<FocusOptions>) {
+        let options = options.into();
+
+        with_panel_state(self as *const _ as usize, |state| {
+            state.focus = options;
+            state.hover.cancel();
+        });
+    }
+
+    pub fn focus_options(&self) -> FocusOptions {
+        with_panel_state(self as *const _ as usize, |state| state.focus)
     }
 
     pub fn is_visible(&self) -> bool {
@@ -147,11 +723,45 @@ impl RawNSPanel {
         flag == YES
     }
 
+    /// Hides the panel if it is visible and shows it otherwise, returning whether it is visible.
+    pub fn toggle(&self) -> bool {
+        if self.is_visible() {
+            self.order_out(None);
+        } else {
+            self.show();
+        }
+
+        self.is_visible()
+    }
+
+    /// Shows the panel unless it is already visible, returning whether it is visible.
+    pub fn show_if_hidden(&self) -> bool {
+        if !self.is_visible() {
+            self.show();
+        }
+
+        self.is_visible()
+    }
+
+    /// Hides the panel if it is visible, returning whether it is visible.
+    pub fn hide_if_visible(&self) -> bool {
+        if self.is_visible() {
+            self.order_out(None);
+        }
+
+        self.is_visible()
+    }
+
     pub fn is_floating_panel(&self) -> bool {
         let flag: BOOL = unsafe { msg_send![self, isFloatingPanel] };
         flag == YES
     }
 
+    pub fn is_key_window(&self) -> bool {
+        let flag: BOOL = unsafe { msg_send![self, isKeyWindow] };
+        flag == YES
+    }
+
     pub fn make_key_window(&self) {
         let _: () = unsafe { msg_send![self, makeKeyWindow] };
     }
@@ -161,15 +771,42 @@ impl RawNSPanel {
     }
 
     pub fn make_key_and_order_front(&self, sender: Option<id>) {
-        let _: () = unsafe { msg_send![self, makeKeyAndOrderFront: sender.unwrap_or(nil)] };
+        self.notify_visibility(|| {
+            let _: () = unsafe { msg_send![self, makeKeyAndOrderFront: sender.unwrap_or(nil)] };
+        });
     }
 
     pub fn order_front_regardless(&self) {
-        let _: () = unsafe { msg_send![self, orderFrontRegardless] };
+        self.notify_visibility(|| {
+            let _: () = unsafe { msg_send![self, orderFrontRegardless] };
+        });
     }
 
     pub fn order_out(&self, sender: Option<id>) {
-        let _: () = unsafe { msg_send![self, orderOut: sender.unwrap_or(nil)] };
+        self.notify_visibility(|| {
+            let _: () = unsafe { msg_send![self, orderOut: sender.unwrap_or(nil)] };
+        });
+    }
+
+    /// Runs `f` and posts the notification behind [`PanelEvent::Shown`] or [`PanelEvent::Hidden`]
+    /// if it changed whether the panel is visible.
+    fn notify_visibility(&self, f: impl FnOnce()) {
+        let was_visible = self.is_visible();
+
+        f();
+
+        let name = match (was_visible, self.is_visible()) {
+            (false, true) => DID_SHOW_NOTIFICATION,
+            (true, false) => DID_HIDE_NOTIFICATION,
+            _ => return,
+        };
+
+        unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let name = NSString::alloc(nil).init_str(name);
+            let _: () = msg_send![center, postNotificationName: name object: self];
+            let _: () = msg_send![name, release];
+        }
     }
 
     pub fn content_view(&self) -> id {
@@ -184,30 +821,192 @@ impl RawNSPanel {
         }
     }
 
-    pub fn set_level(&self, level: i32) {
+    pub fn set_level(&self, level: impl Into<PanelLevel>) {
+        let level = level.into().value() as NSInteger;
         let _: () = unsafe { msg_send![self, setLevel: level] };
     }
 
+    pub fn level(&self) -> PanelLevel {
+        let level: NSInteger = unsafe { msg_send![self, level] };
+        PanelLevel::from(level as i32)
+    }
+
     pub fn set_alpha_value(&self, value: f64) {
         let _: () = unsafe { msg_send![self, setAlphaValue: value] };
     }
 
+    pub fn alpha_value(&self) -> f64 {
+        unsafe { msg_send![self, alphaValue] }
+    }
+
     pub fn set_content_size(&self, width: f64, height: f64) {
         let _: () = unsafe { msg_send![self, setContentSize: (width, height)] };
     }
 
-    pub fn set_style_mask(&self, style_mask: i32) {
+    /// Returns the frame of the panel in logical coordinates, with the origin at the top-left of
+    /// the primary screen like Tauri's.
+    pub fn frame(&self) -> PanelRect {
+        let frame: NSRect = unsafe { msg_send![self, frame] };
+
+        PanelRect::from(frame).to_top_left(primary_screen_height())
+    }
+
+    /// Moves and resizes the panel, `rect` being in the coordinates of [`RawNSPanel::frame`].
+    pub fn set_frame(&self, rect: PanelRect, animate: bool) {
+        let frame: NSRect = rect.to_bottom_left(primary_screen_height()).into();
+
+        let _: () = unsafe { msg_send![self, setFrame: frame display: YES animate: animate] };
+    }
+
+    /// Moves the top-left corner of the panel to `position`, in the coordinates of
+    /// [`RawNSPanel::frame`].
+    pub fn set_position(&self, position: PanelPoint) {
+        let top_left: NSPoint = position.to_bottom_left(primary_screen_height()).into();
+
+        let _: () = unsafe { msg_send![self, setFrameTopLeftPoint: top_left] };
+    }
+
+    /// Centers the panel horizontally, and slightly above the middle vertically, on its screen.
+    pub fn center(&self) {
+        let _: () = unsafe { msg_send![self, center] };
+    }
+
+    /// Returns the frame of the content view, excluding the title bar, in the coordinates of
+    /// [`RawNSPanel::frame`].
+    pub fn content_rect(&self) -> PanelRect {
+        let content_rect: NSRect = unsafe {
+            let frame: NSRect = msg_send![self, frame];
+            msg_send![self, contentRectForFrameRect: frame]
+        };
+
+        PanelRect::from(content_rect).to_top_left(primary_screen_height())
+    }
+
+    /// Moves the panel next to `rect`, in the coordinates of [`RawNSPanel::frame`], keeping it
+    /// within the visible area of the screen `rect` is on. See [`anchor_position`].
+    pub fn anchor_to_rect(&self, rect: PanelRect, options: AnchorOptions) {
+        let screens = screens();
+
+        let Some(screen) = screen::screen_at(&screens, rect.center()) else {
+            return;
+        };
+
+        let position = anchor_position(rect, self.frame().size(), screen.visible_frame, options);
+
+        self.set_position(position);
+    }
+
+    /// Moves the panel under the tray icon that emitted `event`, see
+    /// [`RawNSPanel::anchor_to_rect`].
+    #[cfg(feature = "tray-icon")]
+    pub fn anchor_to_tray(&self, event: &TrayIconEvent, options: AnchorOptions) {
+        let Some(rect) = tray_icon_rect(event) else {
+            return;
+        };
+
+        self.anchor_to_rect(screen::logical_rect(rect, &screens()), options);
+    }
+
+    pub fn set_style_mask(&self, style_mask: impl Into<PanelStyleMask>) {
+        let style_mask = style_mask.into().bits() as NSUInteger;
         let _: () = unsafe { msg_send![self, setStyleMask: style_mask] };
     }
 
-    pub fn set_collection_behaviour(&self, behaviour: NSWindowCollectionBehavior) {
+    pub fn style_mask(&self) -> PanelStyleMask {
+        let style_mask: NSUInteger = unsafe { msg_send![self, styleMask] };
+        PanelStyleMask::from_bits_retain(style_mask as u64)
+    }
+
+    /// Adds `style_mask` to the panel's current style mask.
+    pub fn insert_style_mask(&self, style_mask: PanelStyleMask) {
+        self.set_style_mask(self.style_mask() | style_mask);
+    }
+
+    /// Removes `style_mask` from the panel's current style mask.
+    pub fn remove_style_mask(&self, style_mask: PanelStyleMask) {
+        self.set_style_mask(self.style_mask() - style_mask);
+    }
+
+    pub fn set_collection_behaviour(&self, behaviour: impl Into<PanelCollectionBehavior>) {
+        let behaviour = behaviour.into().bits() as NSUInteger;
         let _: () = unsafe { msg_send![self, setCollectionBehavior: behaviour] };
     }
 
+    pub fn collection_behaviour(&self) -> PanelCollectionBehavior {
+        let behaviour: NSUInteger = unsafe { msg_send![self, collectionBehavior] };
+        PanelCollectionBehavior::from_bits_retain(behaviour as u64)
+    }
+
     pub fn set_delegate<T>(&self, delegate: Id<T>) {
         let _: () = unsafe { msg_send![self, setDelegate: delegate] };
     }
 
+    /// Calls `callback` with every [`PanelEvent`] the panel posts as an `NSWindow` notification.
+    ///
+    /// Unlike [`RawNSPanel::set_delegate`], this leaves the panel's delegate untouched, so it can be
+    /// combined with a delegate created by [`panel_delegate!`](crate::panel_delegate).
+    ///
+    /// The observers are removed once the panel closes, after `callback` receives
+    /// [`PanelEvent::WillClose`], or when it is unregistered or deallocated.
+    ///
+    /// `callback` is `Send + Sync` like on other platforms, where window events arrive on any thread.
+    pub fn on_event<F: Fn(PanelEvent) + Send + Sync + 'static>(&self, callback: F) {
+        let key = self as *const _ as usize;
+        let callback = Rc::new(callback);
+        let registered = Rc::new(RefCell::new(Vec::new()));
+
+        unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+
+            for &(notification_name, delegate_name) in WINDOW_NOTIFICATIONS {
+                let callback = callback.clone();
+                let block_registered = registered.clone();
+                let block = ConcreteBlock::new(move |notification: id| {
+                    let event = PanelEvent::from_notification(delegate_name, notification);
+                    let closing = event == PanelEvent::WillClose;
+
+                    callback(event);
+
+                    if closing {
+                        let observers: Vec<id> = block_registered.take();
+
+                        with_panel_state(key, |state| {
+                            state
+                                .event_observers
+                                .retain(|observer| !observers.contains(observer))
+                        });
+
+                        remove_observers(observers);
+                    }
+                });
+                let block = block.copy();
+
+                let name = NSString::alloc(nil).init_str(notification_name);
+                let observer: id = msg_send![
+                    center,
+                    addObserverForName: name
+                    object: self
+                    queue: nil
+                    usingBlock: &*block
+                ];
+                let _: () = msg_send![name, release];
+
+                registered.borrow_mut().push(msg_send![observer, retain]);
+            }
+        }
+
+        let observers = registered.borrow().clone();
+        with_panel_state(key, |state| state.event_observers.extend(observers));
+    }
+
+    /// Calls `callback` with the mouse events of the panel's tracking area, see
+    /// [`WebviewPanelConfig::with_tracking_area`].
+    pub fn on_mouse<F: Fn(MouseTrackingEvent) + 'static>(&self, callback: F) {
+        with_panel_state(self as *const _ as usize, |state| {
+            state.mouse_listeners.push(Rc::new(callback))
+        });
+    }
+
     pub fn set_floating_panel(&self, value: bool) {
         let _: () = unsafe { msg_send![self, setFloatingPanel: value] };
     }
@@ -225,6 +1024,10 @@ impl RawNSPanel {
     }
 
     pub fn activate(&self) {
+        if let Some(target) = self.screen_target() {
+            self.move_to_screen(target);
+        }
+
         // Configure panel for interaction
         self.set_accepts_mouse_moved_events(true);
         self.set_becomes_key_only_if_needed(true);
@@ -232,8 +1035,9 @@ impl RawNSPanel {
         self.set_hides_on_deactivate(false);
 
         // Make the window visible and activated with higher window level
-        self.set_level(3); // NSFloatingWindowLevel - alternative to set_floating_panel(true)... not sure which to use, nothing changes rn
-                           // also this does the same as order_front_regardless, so one of the two could be removed
+        // alternative to set_floating_panel(true)... not sure which to use, nothing changes rn
+        // also this does the same as order_front_regardless, so one of the two could be removed
+        self.set_level(PanelLevel::Floating);
         self.order_front_regardless();
         self.make_key_and_order_front(None);
         // self.set_floating_panel(true); // maybe this is needed instead? not sure
@@ -276,43 +1080,323 @@ impl RawNSPanel {
         unsafe { ShareId::from_ptr(self as *mut Self) }
     }
 
-    // will do manually cuz this isn't an original NSPanel method... shouldn't have been in the RawNSPanel in the first place...
-    fn add_tracking_area(&self) {
+    /// Adds a tracking area to the content view, owned by the panel so its events reach
+    /// [`RawNSPanel::on_mouse`] and the [`FocusPolicy`](crate::FocusPolicy).
+    ///
+    /// The area is rebuilt whenever the content view resizes, so its rect never goes stale.
+    /// Closing the panel removes every area.
+    pub fn add_tracking_area(&self, area: TrackingArea) -> TrackingAreaId {
+        self.observe_content_view();
+
+        let native = self.native_tracking_area(area);
+        let key = self as *const _ as usize;
+
+        with_panel_state(key, |state| {
+            state.next_tracking_area += 1;
+
+            let tracking_id = TrackingAreaId(state.next_tracking_area);
+            state.tracking_areas.push((tracking_id, area, native));
+
+            tracking_id
+        })
+    }
+
+    /// Returns whether a tracking area was removed.
+    pub fn remove_tracking_area(&self, tracking_id: TrackingAreaId) -> bool {
+        let key = self as *const _ as usize;
+
+        let removed = with_panel_state(key, |state| {
+            let index = state
+                .tracking_areas
+                .iter()
+                .position(|(id, _, _)| *id == tracking_id)?;
+
+            let native = state.tracking_areas.remove(index).2;
+
+            Some((native, state.tracking_areas.is_empty()))
+        });
+
+        let Some((native, was_last)) = removed else {
+            return false;
+        };
+
+        self.drop_native_tracking_area(native);
+
+        if was_last {
+            self.unobserve_content_view();
+        }
+
+        true
+    }
+
+    /// Removes the tracking areas, observers and monitors of the panel.
+    fn remove_registrations(&self) {
+        self.remove_tracking_areas();
+
+        let (event_observers, dismiss_observers) =
+            with_panel_state(self as *const _ as usize, |state| {
+                (
+                    std::mem::take(&mut state.event_observers),
+                    state.dismiss_observers.take(),
+                )
+            });
+
+        remove_observers(event_observers);
+
+        if let Some(observers) = dismiss_observers {
+            observers.remove();
+        }
+    }
+
+    /// Stops the animation and pending hover changes of the panel, and removes its tracking
+    /// areas, observers and monitors. Runs when the panel closes or is unregistered.
+    pub(crate) fn tear_down(&self) {
+        // The animation timer and pending hover changes retain the panel, keep it alive meanwhile
+        let panel = unsafe { ShareId::from_ptr(self as *const Self as *mut Self) };
+        let key = self as *const _ as usize;
+
+        self.remove_registrations();
+
+        let animation = with_panel_state(key, |state| {
+            state.hover.cancel();
+            state.animation.take()
+        });
+
+        if let Some(running) = animation {
+            let _: () = unsafe { msg_send![running.timer, invalidate] };
+            self.set_alpha_value(running.alpha);
+            self.set_frame(running.frame, false);
+        }
+
+        unsafe {
+            let _: () = msg_send![class!(NSObject), cancelPreviousPerformRequestsWithTarget: self];
+        }
+
+        drop(panel);
+    }
+
+    fn remove_tracking_areas(&self) {
+        let areas = with_panel_state(self as *const _ as usize, |state| {
+            std::mem::take(&mut state.tracking_areas)
+        });
+
+        for (_, _, native) in areas {
+            self.drop_native_tracking_area(native);
+        }
+
+        self.unobserve_content_view();
+    }
+
+    /// Replaces the tracking area, keeping its id. Returns whether it existed.
+    pub fn replace_tracking_area(&self, tracking_id: TrackingAreaId, area: TrackingArea) -> bool {
+        let key = self as *const _ as usize;
+
+        let exists = with_panel_state(key, |state| {
+            state
+                .tracking_areas
+                .iter()
+                .any(|(id, _, _)| *id == tracking_id)
+        });
+
+        if !exists {
+            return false;
+        }
+
+        self.observe_content_view();
+
+        let native = self.native_tracking_area(area);
+
+        let previous = with_panel_state(key, |state| {
+            let entry = state
+                .tracking_areas
+                .iter_mut()
+                .find(|(id, _, _)| *id == tracking_id)?;
+
+            let previous = entry.2;
+            *entry = (tracking_id, area, native);
+
+            Some(previous)
+        });
+
+        if let Some(previous) = previous {
+            self.drop_native_tracking_area(previous);
+        }
+
+        true
+    }
+
+    fn native_tracking_area(&self, area: TrackingArea) -> id {
         let view: id = self.content_view();
-        let bounds: NSRect = unsafe { NSViewOld::bounds(view) };
-        let track_view: id = unsafe { msg_send![class!(NSTrackingArea), alloc] };
-        let track_view: id = unsafe {
-            msg_send![
-                track_view,
-                initWithRect: bounds
-                options: NSTrackingAreaOptionsOld::NSTrackingActiveAlways.bits()
-                | NSTrackingAreaOptionsOld::NSTrackingMouseEnteredAndExited.bits()
-                | NSTrackingAreaOptionsOld::NSTrackingMouseMoved.bits()
-                | NSTrackingAreaOptionsOld::NSTrackingCursorUpdate.bits()
+
+        unsafe {
+            let bounds: NSRect = NSViewOld::bounds(view);
+            let flipped: BOOL = msg_send![view, isFlipped];
+            let rect: NSRect = area.rect.resolve(bounds.into(), flipped == YES).into();
+            let options: NSUInteger = area.options.bits() as NSUInteger;
+
+            let native: id = msg_send![class!(NSTrackingArea), alloc];
+            let native: id = msg_send![
+                native,
+                initWithRect: rect
+                options: options
                 owner: self
                 userInfo: nil
-            ]
+            ];
+
+            let () = msg_send![view, addTrackingArea: native];
+
+            native
+        }
+    }
+
+    fn drop_native_tracking_area(&self, native: id) {
+        let view: id = self.content_view();
+
+        unsafe {
+            let () = msg_send![view, removeTrackingArea: native];
+            let () = msg_send![native, release];
+        }
+    }
+
+    /// Observes the frame of the content view, switching over if it was replaced since.
+    fn observe_content_view(&self) {
+        let key = self as *const _ as usize;
+        let view: id = self.content_view();
+
+        if with_panel_state(key, |state| state.observed_content_view) == Some(view) {
+            return;
+        }
+
+        self.unobserve_content_view();
+
+        unsafe {
+            let autoresizing_mask = NSViewWidthSizable | NSViewHeightSizable;
+            let () = msg_send![view, setAutoresizingMask: autoresizing_mask];
+            let () = msg_send![view, setPostsFrameChangedNotifications: YES];
+
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let name = NSString::alloc(nil).init_str("NSViewFrameDidChangeNotification");
+            let () = msg_send![
+                center,
+                addObserver: self
+                selector: sel!(contentViewFrameDidChange:)
+                name: name
+                object: view
+            ];
+            let () = msg_send![name, release];
+            let () = msg_send![view, retain];
+        }
+
+        with_panel_state(key, |state| state.observed_content_view = Some(view));
+    }
+
+    fn unobserve_content_view(&self) {
+        let key = self as *const _ as usize;
+
+        let Some(view) = with_panel_state(key, |state| state.observed_content_view.take()) else {
+            return;
         };
 
-        let autoresizing_mask = NSViewWidthSizable | NSViewHeightSizable;
-        let () = unsafe { msg_send![view, setAutoresizingMask: autoresizing_mask] };
-        let () = unsafe { msg_send![view, addTrackingArea: track_view] };
+        unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let name = NSString::alloc(nil).init_str("NSViewFrameDidChangeNotification");
+            let () = msg_send![
+                center,
+                removeObserver: self
+                name: name
+                object: view
+            ];
+            let () = msg_send![name, release];
+            let () = msg_send![view, release];
+        }
+    }
+
+    /// Rebuilds every tracking area for the new bounds of the content view.
+    extern "C" fn content_view_frame_did_change(this: &Object, _sel: Sel, _notification: id) {
+        let panel = unsafe { &*(this as *const Object as *const RawNSPanel) };
+        let key = this as *const _ as usize;
+
+        let areas = with_panel_state(key, |state| {
+            state
+                .tracking_areas
+                .iter()
+                .map(|(id, area, _)| (*id, *area))
+                .collect::<Vec<_>>()
+        });
+
+        for (tracking_id, area) in areas {
+            panel.replace_tracking_area(tracking_id, area);
+        }
+    }
+
+    /// Applies the settings of `config` that can change after conversion, leaving the ones set to
+    /// `None` untouched. See [`ConversionPolicy::ReuseExisting`].
+    pub(crate) fn apply_config(&self, config: &WebviewPanelConfig) {
+        self.set_focus_options(config.focus);
+        self.set_screen_target(config.screen_target);
+        self.set_dismiss_policy(config.dismiss_policy);
+
+        if let Some(level) = config.level.clone() {
+            self.set_level(level);
+        }
+
+        if let Some(style_mask) = config.style_mask {
+            self.set_style_mask(style_mask);
+        }
+
+        if let Some(behaviour) = config.collection_behavior {
+            self.set_collection_behaviour(behaviour);
+        }
+
+        if let Some(floating) = config.floating {
+            self.set_floating_panel(floating);
+        }
+
+        if let Some(hides_on_deactivate) = config.hides_on_deactivate {
+            self.set_hides_on_deactivate(hides_on_deactivate);
+        }
     }
 
     /// Create an NSPanel from a Tauri Webview Window
     pub fn from_window<R: Runtime>(
         window: WebviewWindow<R>,
         config: WebviewPanelConfig,
-    ) -> Id<Self> {
-        let nswindow: id = window.ns_window().unwrap() as _;
-        let nspanel_class: id = unsafe { msg_send![Self::class(), class] };
+    ) -> Result<Id<Self>, Error> {
+        MainThreadMarker::new().ok_or(Error::NotOnMainThread)?;
+
+        let nswindow: id = window
+            .ns_window()
+            .map_err(|_| Error::NativeHandleUnavailable {
+                label: window.label().into(),
+            })? as _;
+        if Self::is_panel(nswindow) {
+            return match config.conversion_policy {
+                ConversionPolicy::ReuseExisting => {
+                    let panel: Id<RawNSPanel> =
+                        unsafe { Id::from_ptr(nswindow as *mut RawNSPanel) };
+                    panel.apply_config(&config);
+
+                    Ok(panel)
+                }
+                ConversionPolicy::Fail => Err(Error::AlreadyConverted {
+                    label: window.label().into(),
+                }),
+            };
+        }
+
+        let nspanel_class: id = unsafe { msg_send![Self::class_for(config.capabilities), class] };
+
         unsafe {
             object_setClass(nswindow, nspanel_class);
-            let panel = Id::from_retained_ptr(nswindow as *mut RawNSPanel);
+            // Retain the window, the panel is released when it is unregistered from the store
+            let panel: Id<RawNSPanel> = Id::from_ptr(nswindow as *mut RawNSPanel);
 
             if config.with_tracking_area {
                 // Add a tracking area to the panel's content view
-                panel.add_tracking_area();
+                panel.add_tracking_area(TrackingArea {
+                    options: config.tracking_options,
+                    rect: config.tracking_rect,
+                });
             }
 
             // Configure panel to maintain focus - do this immediately
@@ -322,13 +1406,94 @@ impl RawNSPanel {
             panel.set_works_when_modal(true);
 
             // Set to floating window level for better focus retention
-            panel.set_level(3); // NSFloatingWindowLevel = 3
+            if config.level.is_none() {
+                panel.set_level(PanelLevel::default());
+            }
+
+            panel.apply_config(&config);
 
             // panel.make_key_window(); // Make it the key window initially - not needed rn, see comments above
 
-            panel
+            Ok(panel)
         }
     }
 }
 
+/// The screens and cursor as reported by AppKit.
+struct AppKitScreens;
+
+impl ScreenSource for AppKitScreens {
+    fn screens(&self) -> Vec<PanelScreen> {
+        screens()
+    }
+
+    fn cursor_position(&self) -> Option<PanelPoint> {
+        let cursor: NSPoint = unsafe { msg_send![class!(NSEvent), mouseLocation] };
+
+        Some(PanelPoint::from(cursor).to_top_left(primary_screen_height()))
+    }
+
+    fn main_screen(&self) -> Option<usize> {
+        unsafe {
+            let main_screen: id = msg_send![class!(NSScreen), mainScreen];
+            let screens: id = msg_send![class!(NSScreen), screens];
+            let index: NSUInteger = msg_send![screens, indexOfObject: main_screen];
+
+            // `NSNotFound` when there is no main screen
+            (main_screen != nil && index < NSInteger::MAX as NSUInteger).then_some(index as usize)
+        }
+    }
+}
+
+/// Returns every screen, the primary one first.
+pub(crate) fn screens() -> Vec<PanelScreen> {
+    let primary_screen_height = primary_screen_height();
+
+    unsafe {
+        let screens: id = msg_send![class!(NSScreen), screens];
+        let count: NSUInteger = msg_send![screens, count];
+
+        (0..count)
+            .map(|index| {
+                let screen: id = msg_send![screens, objectAtIndex: index];
+                let frame: NSRect = msg_send![screen, frame];
+                let visible_frame: NSRect = msg_send![screen, visibleFrame];
+                let scale_factor: f64 = msg_send![screen, backingScaleFactor];
+
+                PanelScreen {
+                    frame: PanelRect::from(frame).to_top_left(primary_screen_height),
+                    visible_frame: PanelRect::from(visible_frame)
+                        .to_top_left(primary_screen_height),
+                    scale_factor,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether the user asked the system to reduce motion, in the accessibility display settings.
+fn reduce_motion() -> bool {
+    let reduce_motion: BOOL = unsafe {
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        msg_send![workspace, accessibilityDisplayShouldReduceMotion]
+    };
+
+    reduce_motion == YES
+}
+
+/// Height of the primary screen, which both AppKit and Tauri screen coordinates are relative to.
+pub(crate) fn primary_screen_height() -> f64 {
+    unsafe {
+        let screens: id = msg_send![class!(NSScreen), screens];
+        let primary: id = msg_send![screens, firstObject];
+
+        if primary == nil {
+            return 0.0;
+        }
+
+        let frame: NSRect = msg_send![primary, frame];
+        frame.size.height
+    }
+}
+
 unsafe impl Message for RawNSPanel {}
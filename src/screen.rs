@@ -0,0 +1,364 @@
+use serde::{Deserialize, Serialize};
+#[cfg(not(target_os = "macos"))]
+use tauri::Monitor;
+#[cfg(any(feature = "tray-icon", not(target_os = "macos")))]
+use tauri::PhysicalPosition;
+#[cfg(feature = "tray-icon")]
+use tauri::Rect;
+
+use crate::{PanelPoint, PanelRect};
+
+/// A display, in logical coordinates with the origin at the top-left of the primary screen.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PanelScreen {
+    pub frame: PanelRect,
+    /// The frame minus the menu bar and the Dock.
+    pub visible_frame: PanelRect,
+    pub scale_factor: f64,
+}
+
+impl PanelScreen {
+    /// Tauri doesn't report the area left by the menu bar and the Dock, so the visible frame is
+    /// the whole frame.
+    #[cfg(not(target_os = "macos"))]
+    pub(crate) fn from_monitor(monitor: &Monitor) -> Self {
+        let frame =
+            PanelRect::from_physical(*monitor.position(), *monitor.size(), monitor.scale_factor());
+
+        Self {
+            frame,
+            visible_frame: frame,
+            scale_factor: monitor.scale_factor(),
+        }
+    }
+
+    /// Returns the frame of the screen in physical pixels, the unit of tray icon rects.
+    #[cfg(any(feature = "tray-icon", not(target_os = "macos")))]
+    fn physical_frame(&self) -> PanelRect {
+        PanelRect {
+            x: self.frame.x * self.scale_factor,
+            y: self.frame.y * self.scale_factor,
+            width: self.frame.width * self.scale_factor,
+            height: self.frame.height * self.scale_factor,
+        }
+    }
+}
+
+/// Returns the screen containing `point`, or the primary screen if none does.
+pub(crate) fn screen_at(screens: &[PanelScreen], point: PanelPoint) -> Option<&PanelScreen> {
+    screens
+        .iter()
+        .find(|screen| screen.frame.contains(point))
+        .or_else(|| screens.first())
+}
+
+/// Returns the scale factor of the screen containing `position`, in physical pixels.
+#[cfg(any(feature = "tray-icon", not(target_os = "macos")))]
+fn scale_factor_at(screens: &[PanelScreen], position: PhysicalPosition<f64>) -> f64 {
+    let point = PanelPoint {
+        x: position.x,
+        y: position.y,
+    };
+
+    screens
+        .iter()
+        .find(|screen| screen.physical_frame().contains(point))
+        .or_else(|| screens.first())
+        .map_or(1.0, |screen| screen.scale_factor)
+}
+
+/// Converts a physical position reported by Tauri, e.g. the cursor position, to logical
+/// coordinates using the scale factor of the screen it lies on.
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn logical_point(
+    position: PhysicalPosition<f64>,
+    screens: &[PanelScreen],
+) -> PanelPoint {
+    position
+        .to_logical::<f64>(scale_factor_at(screens, position))
+        .into()
+}
+
+/// Converts a rect reported by Tauri, e.g. the rect of a tray icon, to logical coordinates
+/// using the scale factor of the screen it lies on.
+#[cfg(feature = "tray-icon")]
+pub(crate) fn logical_rect(rect: Rect, screens: &[PanelScreen]) -> PanelRect {
+    let scale_factor = scale_factor_at(screens, rect.position.to_physical(1.0));
+
+    PanelRect::new(
+        rect.position.to_logical::<f64>(scale_factor).into(),
+        rect.size.to_logical(scale_factor),
+    )
+}
+
+/// The screens and cursor of a platform, which [`ScreenTarget`]s are resolved against.
+pub(crate) trait ScreenSource {
+    /// Every screen, the primary one first.
+    fn screens(&self) -> Vec<PanelScreen>;
+
+    fn cursor_position(&self) -> Option<PanelPoint>;
+
+    /// Index of the screen containing the key window, if known.
+    fn main_screen(&self) -> Option<usize>;
+}
+
+/// The screen a panel is shown on.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScreenTarget {
+    /// The screen under the mouse cursor, e.g. for a Spotlight-style panel.
+    CursorScreen,
+    /// The screen containing the key window, or the primary screen when unknown.
+    MainScreen,
+    /// The screen containing the largest part of a window frame, e.g. another panel's `frame()`.
+    ScreenOf(PanelRect),
+    /// A screen by index, the primary screen being `0`.
+    Index(usize),
+}
+
+impl ScreenTarget {
+    /// Returns the targeted screen, or the primary screen if it doesn't exist.
+    pub(crate) fn resolve(self, source: &impl ScreenSource) -> Option<PanelScreen> {
+        let screens = source.screens();
+
+        let screen = match self {
+            ScreenTarget::CursorScreen => source
+                .cursor_position()
+                .and_then(|cursor| screens.iter().find(|screen| screen.frame.contains(cursor))),
+            ScreenTarget::MainScreen => source.main_screen().and_then(|index| screens.get(index)),
+            ScreenTarget::ScreenOf(frame) => screen_of(&screens, frame),
+            ScreenTarget::Index(index) => screens.get(index),
+        };
+
+        screen.or_else(|| screens.first()).copied()
+    }
+}
+
+/// Returns the screen containing the largest part of `frame`, or the screen containing its
+/// center if it is entirely off screen.
+pub(crate) fn screen_of(screens: &[PanelScreen], frame: PanelRect) -> Option<&PanelScreen> {
+    screens
+        .iter()
+        .filter_map(|screen| {
+            let area = screen.frame.intersection(&frame)?.area();
+
+            Some((screen, area))
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(screen, _)| screen)
+        .or_else(|| screen_at(screens, frame.center()))
+}
+
+/// Moves `frame` from the screen `from` to the screen `to`, keeping its relative position within
+/// their visible frames.
+///
+/// The logical size is kept, so the panel looks the same on screens with different scale
+/// factors, and only shrinks to fit a smaller screen. All coordinates are logical with a
+/// top-left origin.
+pub fn move_between_screens(frame: PanelRect, from: &PanelScreen, to: &PanelScreen) -> PanelRect {
+    let from = from.visible_frame;
+    let to = to.visible_frame;
+
+    let width = frame.width.min(to.width);
+    let height = frame.height.min(to.height);
+
+    PanelRect {
+        x: to.x + relative_offset(frame.x - from.x, from.width - frame.width) * (to.width - width),
+        y: to.y
+            + relative_offset(frame.y - from.y, from.height - frame.height) * (to.height - height),
+        width,
+        height,
+    }
+}
+
+/// Returns where `offset` lies within `free_space`, from `0.0` to `1.0`, centering when there is
+/// no free space.
+fn relative_offset(offset: f64, free_space: f64) -> f64 {
+    if free_space <= 0.0 {
+        return 0.5;
+    }
+
+    (offset / free_space).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 1440x900 Retina primary screen with a menu bar, and a 1920x1080 screen right of it,
+    /// 180 points higher.
+    struct FakeScreens {
+        cursor: Option<PanelPoint>,
+        main: Option<usize>,
+    }
+
+    const PRIMARY: PanelScreen = PanelScreen {
+        frame: PanelRect {
+            x: 0.0,
+            y: 0.0,
+            width: 1440.0,
+            height: 900.0,
+        },
+        visible_frame: PanelRect {
+            x: 0.0,
+            y: 25.0,
+            width: 1440.0,
+            height: 875.0,
+        },
+        scale_factor: 2.0,
+    };
+
+    const SECONDARY: PanelScreen = PanelScreen {
+        frame: PanelRect {
+            x: 1440.0,
+            y: -180.0,
+            width: 1920.0,
+            height: 1080.0,
+        },
+        visible_frame: PanelRect {
+            x: 1440.0,
+            y: -180.0,
+            width: 1920.0,
+            height: 1080.0,
+        },
+        scale_factor: 1.0,
+    };
+
+    impl ScreenSource for FakeScreens {
+        fn screens(&self) -> Vec<PanelScreen> {
+            vec![PRIMARY, SECONDARY]
+        }
+
+        fn cursor_position(&self) -> Option<PanelPoint> {
+            self.cursor
+        }
+
+        fn main_screen(&self) -> Option<usize> {
+            self.main
+        }
+    }
+
+    fn source(cursor: Option<PanelPoint>, main: Option<usize>) -> FakeScreens {
+        FakeScreens { cursor, main }
+    }
+
+    #[test]
+    fn resolves_cursor_screen() {
+        let on_secondary = source(
+            Some(PanelPoint {
+                x: 2000.0,
+                y: -100.0,
+            }),
+            None,
+        );
+        let off_screen = source(Some(PanelPoint { x: -500.0, y: 0.0 }), None);
+
+        assert_eq!(
+            ScreenTarget::CursorScreen.resolve(&on_secondary),
+            Some(SECONDARY)
+        );
+        assert_eq!(
+            ScreenTarget::CursorScreen.resolve(&off_screen),
+            Some(PRIMARY)
+        );
+        assert_eq!(
+            ScreenTarget::CursorScreen.resolve(&source(None, None)),
+            Some(PRIMARY)
+        );
+    }
+
+    #[test]
+    fn resolves_main_screen() {
+        assert_eq!(
+            ScreenTarget::MainScreen.resolve(&source(None, Some(1))),
+            Some(SECONDARY)
+        );
+        assert_eq!(
+            ScreenTarget::MainScreen.resolve(&source(None, None)),
+            Some(PRIMARY)
+        );
+    }
+
+    #[test]
+    fn resolves_index() {
+        assert_eq!(
+            ScreenTarget::Index(1).resolve(&source(None, None)),
+            Some(SECONDARY)
+        );
+        assert_eq!(
+            ScreenTarget::Index(5).resolve(&source(None, None)),
+            Some(PRIMARY)
+        );
+    }
+
+    #[test]
+    fn resolves_screen_of() {
+        // 140 points on the primary screen, 260 on the secondary one
+        let frame = PanelRect {
+            x: 1300.0,
+            y: 100.0,
+            width: 400.0,
+            height: 300.0,
+        };
+
+        assert_eq!(
+            ScreenTarget::ScreenOf(frame).resolve(&source(None, None)),
+            Some(SECONDARY)
+        );
+    }
+
+    #[test]
+    fn screen_of_off_screen_frame_is_primary() {
+        let frame = PanelRect {
+            x: 5000.0,
+            y: 100.0,
+            width: 400.0,
+            height: 300.0,
+        };
+
+        assert_eq!(screen_of(&[PRIMARY, SECONDARY], frame), Some(&PRIMARY));
+        assert_eq!(screen_of(&[], frame), None);
+    }
+
+    #[test]
+    fn moves_keeping_relative_position() {
+        // Centered horizontally at the bottom of the primary screen
+        let frame = PanelRect {
+            x: 520.0,
+            y: 600.0,
+            width: 400.0,
+            height: 300.0,
+        };
+
+        assert_eq!(
+            move_between_screens(frame, &PRIMARY, &SECONDARY),
+            PanelRect {
+                x: 2200.0,
+                y: 600.0,
+                width: 400.0,
+                height: 300.0,
+            }
+        );
+    }
+
+    #[test]
+    fn moves_and_shrinks_to_fit_a_smaller_screen() {
+        let frame = PanelRect {
+            x: 1600.0,
+            y: -180.0,
+            width: 1600.0,
+            height: 500.0,
+        };
+
+        assert_eq!(
+            move_between_screens(frame, &SECONDARY, &PRIMARY),
+            PanelRect {
+                x: 0.0,
+                y: 25.0,
+                width: 1440.0,
+                height: 500.0,
+            }
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+
+bitflags! {
+    /// Style mask of a panel, mirroring AppKit's `NSWindowStyleMask`.
+    ///
+    /// Serializes to and from flag names, e.g. `"Titled | NonActivatingPanel"`.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub struct PanelStyleMask: u64 {
+        const Titled = 1 << 0;
+        const Closable = 1 << 1;
+        const Miniaturizable = 1 << 2;
+        const Resizable = 1 << 3;
+        const UtilityWindow = 1 << 4;
+        const DocModalWindow = 1 << 6;
+        const NonActivatingPanel = 1 << 7;
+        const UnifiedTitleAndToolbar = 1 << 12;
+        const HUDWindow = 1 << 13;
+        const FullScreen = 1 << 14;
+        const FullSizeContentView = 1 << 15;
+    }
+}
+
+impl PanelStyleMask {
+    /// No title bar or border, the absence of any flag. It isn't a flag itself, a zero flag would
+    /// be contained in every mask and dropped when serializing.
+    pub const BORDERLESS: Self = Self::empty();
+}
+
+impl From<i32> for PanelStyleMask {
+    /// Keeps unknown bits so raw masks round-trip unchanged. The value is read as unsigned, so
+    /// negative values don't set the high bits.
+    fn from(value: i32) -> Self {
+        PanelStyleMask::from_bits_retain(value as u32 as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borderless_is_empty() {
+        assert!(PanelStyleMask::BORDERLESS.is_empty());
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        for mask in [
+            PanelStyleMask::BORDERLESS,
+            PanelStyleMask::NonActivatingPanel,
+            PanelStyleMask::Titled | PanelStyleMask::FullSizeContentView,
+        ] {
+            let json = serde_json::to_string(&mask).unwrap();
+
+            assert_eq!(serde_json::from_str::<PanelStyleMask>(&json).unwrap(), mask);
+        }
+    }
+
+    #[test]
+    fn deserializes_flag_names() {
+        assert_eq!(
+            serde_json::from_str::<PanelStyleMask>(r#""Titled | NonActivatingPanel""#).unwrap(),
+            PanelStyleMask::Titled | PanelStyleMask::NonActivatingPanel
+        );
+        assert_eq!(
+            serde_json::from_str::<PanelStyleMask>(r#""""#).unwrap(),
+            PanelStyleMask::BORDERLESS
+        );
+        assert!(serde_json::from_str::<PanelStyleMask>(r#""Borderless""#).is_err());
+    }
+
+    #[test]
+    fn from_raw_keeps_unknown_bits() {
+        let mask = PanelStyleMask::from(1 << 7 | 1 << 20);
+
+        assert!(mask.contains(PanelStyleMask::NonActivatingPanel));
+        assert_eq!(mask.bits(), 1 << 7 | 1 << 20);
+    }
+
+    #[test]
+    fn from_negative_raw_keeps_low_bits() {
+        assert_eq!(PanelStyleMask::from(-1).bits(), u32::MAX as u64);
+        assert_eq!(PanelStyleMask::from(i32::MIN).bits(), 1 << 31);
+    }
+}
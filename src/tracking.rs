@@ -0,0 +1,185 @@
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+
+use crate::PanelRect;
+
+bitflags! {
+    /// Options of a tracking area, mirroring AppKit's `NSTrackingAreaOptions`.
+    ///
+    /// Serializes to and from flag names, e.g. `"MouseEnteredAndExited | ActiveAlways"`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub struct TrackingOptions: u64 {
+        const MouseEnteredAndExited = 0x01;
+        const MouseMoved = 0x02;
+        const CursorUpdate = 0x04;
+        const ActiveWhenFirstResponder = 0x10;
+        const ActiveInKeyWindow = 0x20;
+        const ActiveInActiveApp = 0x40;
+        const ActiveAlways = 0x80;
+        const AssumeInside = 0x100;
+        const InVisibleRect = 0x200;
+        const EnabledDuringMouseDrag = 0x400;
+    }
+}
+
+impl Default for TrackingOptions {
+    /// The options of the tracking area added by `to_panel`.
+    fn default() -> Self {
+        TrackingOptions::ActiveAlways
+            | TrackingOptions::MouseEnteredAndExited
+            | TrackingOptions::MouseMoved
+            | TrackingOptions::CursorUpdate
+    }
+}
+
+/// The region of the content view a tracking area covers, recomputed whenever the content view
+/// resizes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TrackingRect {
+    /// The whole content view.
+    #[default]
+    Bounds,
+    /// The content view shrunk by each inset, in points.
+    Inset {
+        top: f64,
+        left: f64,
+        bottom: f64,
+        right: f64,
+    },
+    /// A fixed rectangle in content view coordinates.
+    Custom(PanelRect),
+}
+
+impl TrackingRect {
+    /// Returns the rectangle covered within `bounds`, the bounds of the content view. `flipped`
+    /// is whether the content view has its origin at the top, as reported by `isFlipped`.
+    pub fn resolve(&self, bounds: PanelRect, flipped: bool) -> PanelRect {
+        match *self {
+            TrackingRect::Bounds => bounds,
+            TrackingRect::Inset {
+                top,
+                left,
+                bottom,
+                right,
+            } => PanelRect {
+                x: bounds.x + left,
+                y: bounds.y + if flipped { top } else { bottom },
+                width: (bounds.width - left - right).max(0.0),
+                height: (bounds.height - top - bottom).max(0.0),
+            },
+            TrackingRect::Custom(rect) => rect,
+        }
+    }
+}
+
+/// A tracking area to add to the content view of a panel, see
+/// [`RawNSPanel::add_tracking_area`](crate::raw_nspanel::RawNSPanel::add_tracking_area).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TrackingArea {
+    pub options: TrackingOptions,
+    pub rect: TrackingRect,
+}
+
+/// Identifies a tracking area added to a panel, to remove or replace it later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrackingAreaId(pub(crate) u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: PanelRect = PanelRect {
+        x: 0.0,
+        y: 0.0,
+        width: 400.0,
+        height: 300.0,
+    };
+
+    const INSET: TrackingRect = TrackingRect::Inset {
+        top: 10.0,
+        left: 20.0,
+        bottom: 30.0,
+        right: 40.0,
+    };
+
+    #[test]
+    fn bounds_and_custom_ignore_flipping() {
+        let custom = PanelRect {
+            x: 5.0,
+            y: 6.0,
+            width: 7.0,
+            height: 8.0,
+        };
+
+        for flipped in [false, true] {
+            assert_eq!(TrackingRect::Bounds.resolve(BOUNDS, flipped), BOUNDS);
+            assert_eq!(
+                TrackingRect::Custom(custom).resolve(BOUNDS, flipped),
+                custom
+            );
+        }
+    }
+
+    #[test]
+    fn inset_starts_from_the_bottom_when_unflipped() {
+        assert_eq!(
+            INSET.resolve(BOUNDS, false),
+            PanelRect {
+                x: 20.0,
+                y: 30.0,
+                width: 340.0,
+                height: 260.0,
+            }
+        );
+    }
+
+    #[test]
+    fn inset_starts_from_the_top_when_flipped() {
+        assert_eq!(
+            INSET.resolve(BOUNDS, true),
+            PanelRect {
+                x: 20.0,
+                y: 10.0,
+                width: 340.0,
+                height: 260.0,
+            }
+        );
+    }
+
+    #[test]
+    fn inset_is_relative_to_the_bounds_origin() {
+        let bounds = PanelRect {
+            x: 100.0,
+            y: 50.0,
+            ..BOUNDS
+        };
+
+        assert_eq!(
+            INSET.resolve(bounds, false),
+            PanelRect {
+                x: 120.0,
+                y: 80.0,
+                width: 340.0,
+                height: 260.0,
+            }
+        );
+    }
+
+    #[test]
+    fn insets_larger_than_the_bounds_resolve_to_an_empty_rect() {
+        let inset = TrackingRect::Inset {
+            top: 200.0,
+            left: 300.0,
+            bottom: 200.0,
+            right: 300.0,
+        };
+
+        for flipped in [false, true] {
+            let rect = inset.resolve(BOUNDS, flipped);
+
+            assert_eq!(rect.width, 0.0);
+            assert_eq!(rect.height, 0.0);
+        }
+    }
+}